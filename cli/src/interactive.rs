@@ -0,0 +1,272 @@
+/*!
+Interactive correction (`--interactive`)
+
+Walks through the misspellings of a file one at a time, showing the line with
+the word underlined and numbered suggestions, and asks what to do:
+
+    1-9  replace with that suggestion      r  replace with typed text
+    i    ignore this occurrence            I  ignore every occurrence
+    a    add to the personal dictionary    x  save and stop
+    q    stop without saving
+
+`correct` returns the corrected text for the caller to write back, and
+`correct_files` runs it over every input file, saving each one; `x` saves the
+current file and skips the rest.
+*/
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+
+use crate::{read_input, Checker};
+
+const MENU: &str =
+    "[1-9] replace  r) replace with...  i) ignore  I) ignore all  a) add  x) save & exit  q) quit";
+
+/// How an interactive pass over a text ended.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    /// Every misspelling was handled; the corrected text.
+    Done(String),
+    /// `x`: the text corrected so far, to be saved before stopping.
+    SaveAndStop(String),
+    /// `q`, or `input` ended: discard the edits and stop.
+    Quit,
+}
+
+/// Correct each file of `paths` interactively (see `correct`), writing back
+/// the files that changed. Stops after `x`, `q` or the end of `input`.
+pub(crate) fn correct_files<R: BufRead, W: Write>(
+    checker: &mut Checker,
+    paths: &[String],
+    input: &mut R,
+    out: &mut W,
+) -> io::Result<()> {
+    for path in paths {
+        let text = read_input(path)?;
+        writeln!(out, "== {}", path)?;
+        let (corrected, stop) = match correct(checker, &text, input, out)? {
+            Outcome::Done(corrected) => (corrected, false),
+            Outcome::SaveAndStop(corrected) => (corrected, true),
+            Outcome::Quit => {
+                writeln!(out, "{} left unchanged", path)?;
+                break;
+            }
+        };
+        if corrected != text {
+            fs::write(path, corrected)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+            writeln!(out, "saved {}", path)?;
+        }
+        if stop {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Correct `text` interactively, reading answers from `input` and writing
+/// prompts to `out`.
+pub(crate) fn correct<R: BufRead, W: Write>(
+    checker: &mut Checker,
+    text: &str,
+    input: &mut R,
+    out: &mut W,
+) -> io::Result<Outcome> {
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let mut ignored: HashSet<String> = HashSet::new();
+
+    'words: for m in checker.check(text) {
+        let key = m.token.to_lowercase();
+        if ignored.contains(&key) || checker.ignores(&m.token) {
+            continue;
+        }
+        show(out, text, m.line, &m.byte_range)?;
+        for (i, s) in m.suggestions.iter().take(9).enumerate() {
+            write!(out, "  {}) {}", i + 1, s.term)?;
+        }
+        if !m.suggestions.is_empty() {
+            writeln!(out)?;
+        }
+
+        loop {
+            write!(out, "{}\n> ", MENU)?;
+            out.flush()?;
+            let Some(answer) = read_line(input)? else {
+                return Ok(Outcome::Quit);
+            };
+            match answer.as_str() {
+                "i" => continue 'words,
+                "I" => {
+                    ignored.insert(key);
+                    continue 'words;
+                }
+                "a" => match checker.add_personal(&m.token) {
+                    Ok(()) => continue 'words,
+                    // Keep the session (and the edits made so far) alive.
+                    Err(e) => writeln!(out, "cannot add '{}': {}", m.token, e)?,
+                },
+                "r" => {
+                    write!(out, "replace '{}' with: ", m.token)?;
+                    out.flush()?;
+                    let Some(replacement) = read_line(input)? else {
+                        return Ok(Outcome::Quit);
+                    };
+                    if !replacement.is_empty() {
+                        edits.push((m.byte_range.clone(), replacement));
+                        continue 'words;
+                    }
+                }
+                "x" => return Ok(Outcome::SaveAndStop(apply(text, &edits))),
+                "q" => return Ok(Outcome::Quit),
+                n => {
+                    let choice = n.parse::<usize>().ok().filter(|n| *n >= 1);
+                    if let Some(s) = choice.and_then(|n| m.suggestions.get(n - 1)) {
+                        edits.push((m.byte_range.clone(), s.term.clone()));
+                        continue 'words;
+                    }
+                    writeln!(out, "unknown answer: {:?}", n)?;
+                }
+            }
+        }
+    }
+    Ok(Outcome::Done(apply(text, &edits)))
+}
+
+/// Print the line holding `range` with the word underlined.
+fn show<W: Write>(out: &mut W, text: &str, line: usize, range: &Range<usize>) -> io::Result<()> {
+    let start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[range.end..]
+        .find('\n')
+        .map_or(text.len(), |i| range.end + i);
+    let indent = text[start..range.start].chars().count();
+    let width = text[range.clone()].chars().count();
+    writeln!(out)?;
+    writeln!(out, "{:>5} | {}", line, &text[start..end])?;
+    writeln!(out, "      | {}{}", " ".repeat(indent), "^".repeat(width))
+}
+
+/// One trimmed line of input, or `None` at end of input.
+fn read_line<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// `text` with each (ascending, non-overlapping) range replaced.
+fn apply(text: &str, edits: &[(Range<usize>, String)]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, replacement) in edits {
+        out.push_str(&text[last..range.start]);
+        out.push_str(replacement);
+        last = range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Args;
+
+    #[test]
+    fn test_interactive_session() {
+        let personal =
+            std::env::temp_dir().join(format!("symspellrs-personal-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&personal);
+        let args = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            personal: Some(personal.to_string_lossy().into_owned()),
+            ..Args::default()
+        };
+        let mut checker = Checker::new(&args).unwrap();
+        let text = "Wrld tset\nzork wrld tset zork\nappel\n";
+
+        // Wrld: pick 1; tset: unknown answer, then type a replacement;
+        // zork: add; wrld: ignore; tset: ignore all; appel: pick 1.
+        let mut input = "1\n?\nr\ntest\na\ni\nI\n1\n".as_bytes();
+        let mut out = Vec::new();
+        let corrected = correct(&mut checker, text, &mut input, &mut out).unwrap();
+        assert_eq!(
+            corrected,
+            Outcome::Done("World test\nzork wrld tset zork\napple\n".to_string())
+        );
+        let prompts = String::from_utf8(out).unwrap();
+        assert!(prompts.contains("    1 | Wrld tset\n      | ^^^^\n  1) World"));
+        assert!(prompts.contains("      |      ^^^^\n"));
+        assert!(prompts.contains("unknown answer: \"?\""));
+        assert_eq!(fs::read_to_string(&personal).unwrap(), "zork\n");
+        assert!(checker.ignores("Zork"));
+
+        // Quitting, or running out of input, discards the edits.
+        let mut input = "1\nq\n".as_bytes();
+        assert_eq!(
+            correct(&mut checker, text, &mut input, &mut Vec::new()).unwrap(),
+            Outcome::Quit
+        );
+        let mut input = "".as_bytes();
+        assert_eq!(
+            correct(&mut checker, text, &mut input, &mut Vec::new()).unwrap(),
+            Outcome::Quit
+        );
+        // `x` keeps the edits made so far.
+        let mut input = "1\nx\n".as_bytes();
+        assert_eq!(
+            correct(&mut checker, text, &mut input, &mut Vec::new()).unwrap(),
+            Outcome::SaveAndStop("World tset\nzork wrld tset zork\nappel\n".to_string())
+        );
+        fs::remove_file(&personal).unwrap();
+    }
+
+    #[test]
+    fn test_interactive_add_failure_keeps_session() {
+        let args = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            ..Args::default()
+        };
+        let mut checker = Checker::new(&args).unwrap();
+        // No personal dictionary: adding tset fails and the word is asked again.
+        let mut input = "1\na\nr\ntest\n".as_bytes();
+        let mut out = Vec::new();
+        let corrected = correct(&mut checker, "Wrld tset", &mut input, &mut out).unwrap();
+        assert_eq!(corrected, Outcome::Done("World test".to_string()));
+        let prompts = String::from_utf8(out).unwrap();
+        assert!(prompts.contains("cannot add 'tset': no personal dictionary (--personal)\n"));
+        assert_eq!(prompts.matches(MENU).count(), 3);
+    }
+
+    #[test]
+    fn test_save_and_stop_skips_remaining_files() {
+        let dir = std::env::temp_dir();
+        let paths: Vec<String> = ["first", "second"]
+            .iter()
+            .map(|name| {
+                let path = dir.join(format!("symspellrs-{}-{}.txt", name, std::process::id()));
+                fs::write(&path, "Wrld tset\n").unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        let args = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            ..Args::default()
+        };
+        let mut checker = Checker::new(&args).unwrap();
+
+        // Fix the first word, then save and exit before the first file is done.
+        let mut input = "1\nx\n".as_bytes();
+        let mut out = Vec::new();
+        correct_files(&mut checker, &paths, &mut input, &mut out).unwrap();
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "World tset\n");
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "Wrld tset\n");
+        let prompts = String::from_utf8(out).unwrap();
+        assert!(!prompts.contains(&format!("== {}", paths[1])));
+        for path in &paths {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,430 @@
+/*!
+symspellrs command-line spell checker
+
+Loads a dictionary file, checks the given files (or stdin) and prints one line
+per misspelling:
+
+    path:line:col: word -> suggestion, suggestion
+
+`--format jsonl` prints one JSON object per misspelling instead and
+`--format sarif` a single SARIF 2.1.0 log covering all inputs.
+`--interactive` walks through each file's misspellings asking for a
+correction and writes the corrected file back (see `interactive`).
+
+The exit status is 0 when no misspellings were found, 1 when some were and 2
+on usage or I/O errors, so the checker can gate pre-commit hooks and CI.
+*/
+
+use std::collections::HashSet;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use symspellrs::{
+    write_json_lines, CheckOptions, IdentifierTokenizer, LoadOptions, Lookup, Misspelling,
+    SarifReport, SymSpell, Verbosity,
+};
+
+mod interactive;
+
+const USAGE: &str = "\
+Usage: symspellrs --dictionary <FILE> [OPTIONS] [FILE]...
+
+Check FILEs (or stdin, also given as `-`) for misspelled words.
+
+Options:
+  -d, --dictionary <FILE>   Dictionary file, one word per line (repeatable)
+      --freq                Dictionary lines are `word frequency`
+      --lowercase           Lower-case dictionary words
+  -m, --max-distance <N>    Maximum edit distance of suggestions [default: 2]
+  -v, --verbosity <LEVEL>   top, closest or all [default: closest]
+  -n, --suggestions <N>     Suggestions shown per word, 0 for none [default: 5]
+  -i, --ignore <FILE>       File of words never to report (repeatable)
+  -w, --ignore-word <WORD>  Word never to report (repeatable)
+  -p, --personal <FILE>     Personal dictionary: words never to report, and where
+                            interactive `add` appends
+                            [default with --interactive: ~/.symspellrs-personal.txt]
+  -I, --interactive         Correct each FILE interactively and save the result
+      --code                Split identifiers (camelCase, snake_case, ...) into subwords
+  -f, --format <FORMAT>     text, jsonl or sarif [default: text]
+  -q, --quiet               Print nothing; only set the exit status
+  -h, --help                Print this help
+  -V, --version             Print the version
+";
+
+/// How misspellings are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Format {
+    #[default]
+    Text,
+    JsonLines,
+    Sarif,
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Args {
+    dictionaries: Vec<String>,
+    has_freq: bool,
+    lowercase: bool,
+    max_distance: u8,
+    verbosity: Verbosity,
+    suggestions: usize,
+    ignore_files: Vec<String>,
+    ignore_words: Vec<String>,
+    personal: Option<String>,
+    interactive: bool,
+    code: bool,
+    format: Format,
+    quiet: bool,
+    inputs: Vec<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            dictionaries: Vec::new(),
+            has_freq: false,
+            lowercase: false,
+            max_distance: 2,
+            verbosity: Verbosity::Closest,
+            suggestions: 5,
+            ignore_files: Vec::new(),
+            ignore_words: Vec::new(),
+            personal: None,
+            interactive: false,
+            code: false,
+            format: Format::Text,
+            quiet: false,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+/// What the command line asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Check(Args),
+    Help,
+    Version,
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    let mut only_inputs = false;
+    while let Some(arg) = args.next() {
+        if only_inputs || arg == "-" || !arg.starts_with('-') {
+            parsed.inputs.push(arg);
+            continue;
+        }
+        // Accept both `--flag value` and `--flag=value`.
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline {
+                Some(v) => Ok(v.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name)),
+            }
+        };
+        match flag.as_str() {
+            "--" => only_inputs = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-d" | "--dictionary" => parsed.dictionaries.push(value(&flag)?),
+            "--freq" => parsed.has_freq = true,
+            "--lowercase" => parsed.lowercase = true,
+            "-m" | "--max-distance" => {
+                let v = value(&flag)?;
+                parsed.max_distance = v
+                    .parse()
+                    .map_err(|_| format!("invalid max distance: {}", v))?;
+            }
+            "-v" | "--verbosity" => {
+                parsed.verbosity = match value(&flag)?.as_str() {
+                    "top" => Verbosity::Top,
+                    "closest" => Verbosity::Closest,
+                    "all" => Verbosity::All,
+                    other => return Err(format!("invalid verbosity: {}", other)),
+                }
+            }
+            "-n" | "--suggestions" => {
+                let v = value(&flag)?;
+                parsed.suggestions = v
+                    .parse()
+                    .map_err(|_| format!("invalid suggestion count: {}", v))?;
+            }
+            "-i" | "--ignore" => parsed.ignore_files.push(value(&flag)?),
+            "-w" | "--ignore-word" => parsed.ignore_words.push(value(&flag)?),
+            "-p" | "--personal" => parsed.personal = Some(value(&flag)?),
+            "-I" | "--interactive" => parsed.interactive = true,
+            "--code" => parsed.code = true,
+            "-f" | "--format" => {
+                parsed.format = match value(&flag)?.as_str() {
+                    "text" => Format::Text,
+                    "jsonl" => Format::JsonLines,
+                    "sarif" => Format::Sarif,
+                    other => return Err(format!("invalid format: {}", other)),
+                }
+            }
+            "-q" | "--quiet" => parsed.quiet = true,
+            _ => return Err(format!("unknown option: {}", arg)),
+        }
+    }
+    if parsed.dictionaries.is_empty() {
+        return Err("missing --dictionary".to_string());
+    }
+    if parsed.interactive && (parsed.inputs.is_empty() || parsed.inputs.iter().any(|i| i == "-")) {
+        return Err("--interactive needs files; stdin answers the prompts".to_string());
+    }
+    if parsed.interactive && parsed.personal.is_none() {
+        parsed.personal = std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".symspellrs-personal.txt")
+                .to_string_lossy()
+                .into_owned()
+        });
+    }
+    Ok(Command::Check(parsed))
+}
+
+/// Loaded dictionary plus everything needed to check inputs.
+struct Checker {
+    sym: SymSpell,
+    options: CheckOptions,
+    ignore: HashSet<String>,
+    /// Personal dictionary file, appended to by `add_personal`.
+    personal: Option<PathBuf>,
+}
+
+impl Checker {
+    fn new(args: &Args) -> io::Result<Self> {
+        let load = LoadOptions::new()
+            .with_freq(args.has_freq)
+            .with_lowercase(args.lowercase);
+        let mut sym = SymSpell::new(args.max_distance);
+        for path in &args.dictionaries {
+            sym.load_file(path, &load)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+        }
+
+        let mut ignore: HashSet<String> = args.ignore_words.iter().cloned().collect();
+        let personal = args.personal.as_ref().map(PathBuf::from);
+        // A personal dictionary that does not exist yet is created on first `add`.
+        let existing_personal = args.personal.iter().filter(|p| Path::new(p).exists());
+        for path in args.ignore_files.iter().chain(existing_personal) {
+            let text = fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+            ignore.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+
+        let mut options = CheckOptions::new()
+            .with_max_distance(args.max_distance)
+            .with_verbosity(args.verbosity)
+            .with_max_suggestions(Some(args.suggestions));
+        if args.code {
+            options = options.with_tokenizer(IdentifierTokenizer);
+        }
+        Ok(Self {
+            sym,
+            options,
+            ignore,
+            personal,
+        })
+    }
+
+    /// Returns true if `token` is on the ignore list or in the personal dictionary.
+    fn ignores(&self, token: &str) -> bool {
+        self.ignore.contains(token) || self.ignore.contains(&token.to_lowercase())
+    }
+
+    /// Append `word` to the personal dictionary and stop reporting it.
+    fn add_personal(&mut self, word: &str) -> io::Result<()> {
+        let path = self.personal.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no personal dictionary (--personal)",
+            )
+        })?;
+        let word = word.to_lowercase();
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", word)?;
+        self.ignore.insert(word);
+        Ok(())
+    }
+
+    /// Misspellings of `text` that are not on the ignore list.
+    fn check(&self, text: &str) -> Vec<Misspelling> {
+        self.sym
+            .check_text_with(text, self.options.clone())
+            .filter(|m| !self.ignores(&m.token))
+            .collect()
+    }
+}
+
+/// Write `path:line:col: word -> suggestions` for each misspelling.
+fn report<W: Write>(out: &mut W, path: &str, misspellings: &[Misspelling]) -> io::Result<()> {
+    for m in misspellings {
+        write!(out, "{}:{}:{}: {}", path, m.line, m.column, m.token)?;
+        if !m.suggestions.is_empty() {
+            let terms: Vec<&str> = m.suggestions.iter().map(|s| s.term.as_str()).collect();
+            write!(out, " -> {}", terms.join(", "))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        fs::read_to_string(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))
+    }
+}
+
+/// Correct each input interactively on stdin/stdout.
+fn run_interactive(args: &Args, checker: &mut Checker) -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    interactive::correct_files(checker, &args.inputs, &mut stdin.lock(), &mut stdout.lock())
+}
+
+/// Check every input, returning whether any misspelling was found.
+fn run(args: Args) -> io::Result<bool> {
+    let mut checker = Checker::new(&args)?;
+    if args.interactive {
+        run_interactive(&args, &mut checker)?;
+        return Ok(false);
+    }
+    let inputs = if args.inputs.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        args.inputs.clone()
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut found = false;
+    let mut sarif = SarifReport::new();
+    for path in &inputs {
+        let misspellings = checker.check(&read_input(path)?);
+        found |= !misspellings.is_empty();
+        if args.quiet {
+            continue;
+        }
+        let name = if path == "-" { "<stdin>" } else { path };
+        match args.format {
+            Format::Text => report(&mut out, name, &misspellings)?,
+            Format::JsonLines => write_json_lines(&mut out, name, &misspellings)?,
+            Format::Sarif => sarif.add(name, &misspellings),
+        }
+    }
+    if args.format == Format::Sarif && !args.quiet {
+        sarif.write(&mut out)?;
+    }
+    out.flush()?;
+    Ok(found)
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!("symspellrs {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Check(args)) => match run(args) {
+            Ok(false) => ExitCode::SUCCESS,
+            Ok(true) => ExitCode::from(1),
+            Err(e) => {
+                eprintln!("symspellrs: {}", e);
+                ExitCode::from(2)
+            }
+        },
+        Err(e) => {
+            eprintln!("symspellrs: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Command, String> {
+        parse_args(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let Command::Check(parsed) = args(&[
+            "-d",
+            "words.txt",
+            "--max-distance=1",
+            "-v",
+            "top",
+            "-w",
+            "symspellrs",
+            "--code",
+            "--format=sarif",
+            "a.rs",
+            "-",
+            "--",
+            "--odd-name",
+        ])
+        .unwrap() else {
+            panic!("expected a check command");
+        };
+        assert_eq!(parsed.dictionaries, ["words.txt"]);
+        assert_eq!(parsed.max_distance, 1);
+        assert_eq!(parsed.verbosity, Verbosity::Top);
+        assert_eq!(parsed.ignore_words, ["symspellrs"]);
+        assert!(parsed.code);
+        assert_eq!(parsed.format, Format::Sarif);
+        assert_eq!(parsed.inputs, ["a.rs", "-", "--odd-name"]);
+
+        assert_eq!(args(&["--help"]), Ok(Command::Help));
+        assert!(args(&["a.txt"]).unwrap_err().contains("--dictionary"));
+        assert!(args(&["-d"]).unwrap_err().contains("expects a value"));
+        assert!(args(&["-d", "w", "-v", "loud"]).is_err());
+        assert!(args(&["-d", "w", "--bogus"]).is_err());
+        assert!(args(&["-d", "w", "-f", "xml"]).is_err());
+        assert!(args(&["-d", "w", "--interactive"]).is_err());
+        assert!(args(&["-d", "w", "-I", "a.txt", "-"]).is_err());
+    }
+
+    #[test]
+    fn test_check_and_report() {
+        let parsed = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            ignore_words: vec!["Wrold".to_string()],
+            ..Args::default()
+        };
+        let checker = Checker::new(&parsed).unwrap();
+        let found = checker.check("hello wrld\napple Wrold tset\n");
+        let mut out = Vec::new();
+        report(&mut out, "notes.txt", &found).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "notes.txt:1:7: wrld -> world\nnotes.txt:2:13: tset -> test\n"
+        );
+    }
+}
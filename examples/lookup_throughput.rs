@@ -0,0 +1,152 @@
+/*!
+lookup_throughput.rs
+
+Measures `SymSpell::lookup` throughput on a large synthetic dictionary and
+compares the built-in bounded optimal string alignment metric against a
+full-table DP (the verification strategy used before `distance_within`).
+
+Run in release mode:
+    cargo run --release --example lookup_throughput [words] [queries]
+*/
+
+use std::time::Instant;
+
+use symspellrs::{DistanceMetric, OptimalStringAlignment, SymSpell, Verbosity};
+
+/// Full `(m+1) x (n+1)` table OSA, allocating per call and never stopping early.
+struct FullTableOsa;
+
+impl DistanceMetric for FullTableOsa {
+    fn distance(&self, a: &str, b: &str) -> u8 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for (i, row) in dp.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in dp[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                dp[i][j] = (dp[i - 1][j] + 1)
+                    .min(dp[i][j - 1] + 1)
+                    .min(dp[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        dp[a.len()][b.len()].min(255) as u8
+    }
+}
+
+/// Deterministic pseudo-random numbers so runs are comparable.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, n: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % n
+    }
+
+    fn word(&mut self) -> String {
+        let len = 4 + self.next(9);
+        (0..len)
+            .map(|_| (b'a' + self.next(26) as u8) as char)
+            .collect()
+    }
+
+    /// Apply one or two random edits to `word`.
+    fn misspell(&mut self, word: &str) -> String {
+        let mut chars: Vec<char> = word.chars().collect();
+        for _ in 0..1 + self.next(2) {
+            let i = self.next(chars.len() as u64) as usize;
+            let c = (b'a' + self.next(26) as u8) as char;
+            match self.next(3) {
+                0 => chars[i] = c,
+                1 => chars.insert(i, c),
+                _ if chars.len() > 1 => {
+                    chars.remove(i);
+                }
+                _ => {}
+            }
+        }
+        chars.into_iter().collect()
+    }
+}
+
+fn bench(name: &str, sym: &SymSpell, queries: &[String]) {
+    let start = Instant::now();
+    let mut found = 0usize;
+    for q in queries {
+        found += sym.lookup(q, 2, Verbosity::Closest).len();
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<22} {:>8} lookups in {:>8.2?} ({:>9.0} lookups/s, {} suggestions)",
+        name,
+        queries.len(),
+        elapsed,
+        queries.len() as f64 / elapsed.as_secs_f64(),
+        found
+    );
+}
+
+/// Time verifying `pairs` with `metric.distance_within`, as `lookup` does.
+fn bench_verify(name: &str, metric: &dyn DistanceMetric, pairs: &[(String, String)]) {
+    let start = Instant::now();
+    let mut within = 0usize;
+    for (a, b) in pairs {
+        within += usize::from(metric.distance_within(a, b, 2).is_some());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<22} {:>8} candidates in {:>8.2?} ({:>9.0} candidates/s, {} within 2)",
+        name,
+        pairs.len(),
+        elapsed,
+        pairs.len() as f64 / elapsed.as_secs_f64(),
+        within
+    );
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let words: usize = args.next().and_then(|a| a.parse().ok()).unwrap_or(50_000);
+    let queries: usize = args.next().and_then(|a| a.parse().ok()).unwrap_or(20_000);
+
+    let mut rng = Lcg(42);
+    let dictionary: Vec<String> = (0..words).map(|_| rng.word()).collect();
+    let queries: Vec<String> = (0..queries)
+        .map(|i| rng.misspell(&dictionary[i % dictionary.len()]))
+        .collect();
+
+    println!("building dictionary of {} words...", dictionary.len());
+    let entries = || dictionary.iter().map(|w| (w.clone(), 1usize));
+    let bounded = SymSpell::from_iter(2, entries());
+    let full = SymSpell::from_iter(2, entries()).with_metric(FullTableOsa);
+
+    println!("\n== candidate verification ==");
+    // Pair each query with many dictionary words, as the deletion index would.
+    let pairs: Vec<(String, String)> = queries
+        .iter()
+        .take(2_000)
+        .flat_map(|q| {
+            dictionary
+                .iter()
+                .step_by((dictionary.len() / 100).max(1))
+                .map(move |w| (q.clone(), w.clone()))
+        })
+        .collect();
+    bench_verify("full-table OSA", &FullTableOsa, &pairs);
+    bench_verify("bounded bit-parallel", &OptimalStringAlignment, &pairs);
+
+    println!("\n== end-to-end lookup ==");
+    bench("full-table OSA", &full, &queries);
+    bench("bounded bit-parallel", &bounded, &queries);
+}
@@ -0,0 +1,575 @@
+/*!
+symspellrs language server
+
+Speaks the Language Server Protocol over stdio and checks every open document
+with `check_text`:
+
+- each misspelling is published as a diagnostic (source `symspellrs`);
+- the diagnostic's suggestions are offered as quick-fix code actions;
+- an "Add to workspace dictionary" action runs the `symspellrs.addToDictionary`
+  command, which appends the word to a file in the workspace root (by default
+  `.symspellrs-dictionary.txt`), learns it and re-checks open documents.
+
+Editors launch it as e.g. `symspellrs-lsp --dictionary words.txt`.
+*/
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, ExecuteCommand, Request as LspRequest};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Command, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    ExecuteCommandOptions, ExecuteCommandParams, InitializeParams, NumberOrString, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use serde_json::{json, Value};
+use symspellrs::{CheckOptions, IdentifierTokenizer, LoadOptions, Lookup, Misspelling, SymSpell};
+
+const USAGE: &str = "\
+Usage: symspellrs-lsp --dictionary <FILE> [OPTIONS]
+
+Language server publishing spelling diagnostics over stdio.
+
+Options:
+  -d, --dictionary <FILE>            Dictionary file, one word per line (repeatable)
+      --freq                         Dictionary lines are `word frequency`
+      --lowercase                    Lower-case dictionary words
+  -m, --max-distance <N>             Maximum edit distance of suggestions [default: 2]
+      --workspace-dictionary <NAME>  Word list in the workspace root
+                                     [default: .symspellrs-dictionary.txt]
+      --code                         Split identifiers (camelCase, snake_case, ...) into subwords
+  -h, --help                         Print this help
+";
+
+/// Diagnostic source and code attached to every misspelling.
+const SOURCE: &str = "symspellrs";
+const CODE: &str = "spelling";
+
+/// Command behind the "Add to workspace dictionary" code action.
+const ADD_TO_DICTIONARY: &str = "symspellrs.addToDictionary";
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Args {
+    dictionaries: Vec<String>,
+    has_freq: bool,
+    lowercase: bool,
+    max_distance: u8,
+    workspace_dictionary: String,
+    code: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            dictionaries: Vec::new(),
+            has_freq: false,
+            lowercase: false,
+            max_distance: 2,
+            workspace_dictionary: ".symspellrs-dictionary.txt".to_string(),
+            code: false,
+        }
+    }
+}
+
+/// `None` when help was requested.
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline {
+                Some(v) => Ok(v.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name)),
+            }
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(None),
+            // Passed by most editor clients; stdio is the only transport.
+            "--stdio" => {}
+            "-d" | "--dictionary" => parsed.dictionaries.push(value(&flag)?),
+            "--freq" => parsed.has_freq = true,
+            "--lowercase" => parsed.lowercase = true,
+            "-m" | "--max-distance" => {
+                let v = value(&flag)?;
+                parsed.max_distance = v
+                    .parse()
+                    .map_err(|_| format!("invalid max distance: {}", v))?;
+            }
+            "--workspace-dictionary" => parsed.workspace_dictionary = value(&flag)?,
+            "--code" => parsed.code = true,
+            _ => return Err(format!("unknown option: {}", arg)),
+        }
+    }
+    if parsed.dictionaries.is_empty() {
+        return Err("missing --dictionary".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+/// Dictionary, open documents and the workspace word list.
+struct Server {
+    sym: SymSpell,
+    options: CheckOptions,
+    /// File words are added to; `None` without a workspace root.
+    workspace_dictionary: Option<PathBuf>,
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn new(args: &Args, root: Option<&Path>) -> io::Result<Self> {
+        let load = LoadOptions::new()
+            .with_freq(args.has_freq)
+            .with_lowercase(args.lowercase);
+        let mut sym = SymSpell::new(args.max_distance);
+        for path in &args.dictionaries {
+            sym.load_file(path, &load)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+        }
+
+        let workspace_dictionary = root.map(|root| root.join(&args.workspace_dictionary));
+        if let Some(path) = workspace_dictionary.as_ref().filter(|p| p.exists()) {
+            sym.load_file(path, &LoadOptions::new().with_lowercase(true))
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        }
+
+        let mut options = CheckOptions::new().with_max_distance(args.max_distance);
+        if args.code {
+            options = options.with_tokenizer(IdentifierTokenizer);
+        }
+        Ok(Self {
+            sym,
+            options,
+            workspace_dictionary,
+            documents: HashMap::new(),
+        })
+    }
+
+    fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![ADD_TO_DICTIONARY.to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn handle_notification(&mut self, not: Notification) -> Vec<Message> {
+        match not.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) =
+                    not.extract::<DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents
+                    .insert(uri.clone(), params.text_document.text);
+                vec![self.publish(&uri)]
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(params) =
+                    not.extract::<DidChangeTextDocumentParams>(DidChangeTextDocument::METHOD)
+                else {
+                    return Vec::new();
+                };
+                // Full sync: the last change holds the whole document.
+                let Some(change) = params.content_changes.into_iter().last() else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), change.text);
+                vec![self.publish(&uri)]
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(params) =
+                    not.extract::<DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                vec![diagnostics_notification(uri, Vec::new())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_request(&mut self, req: Request) -> Vec<Message> {
+        let id = req.id.clone();
+        let response = match req.method.as_str() {
+            CodeActionRequest::METHOD => {
+                match req.extract::<CodeActionParams>(CodeActionRequest::METHOD) {
+                    Ok((id, params)) => Response::new_ok(id, self.code_actions(&params)),
+                    Err(e) => invalid_params(id, e),
+                }
+            }
+            ExecuteCommand::METHOD => {
+                match req.extract::<ExecuteCommandParams>(ExecuteCommand::METHOD) {
+                    Ok((id, params)) => return self.execute_command(id, params),
+                    Err(e) => invalid_params(id, e),
+                }
+            }
+            _ => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unhandled method: {}", req.method),
+            ),
+        };
+        vec![Message::Response(response)]
+    }
+
+    /// `publishDiagnostics` for the current text of `uri`.
+    fn publish(&self, uri: &Url) -> Message {
+        let text = self.documents.get(uri).map(String::as_str).unwrap_or("");
+        let diagnostics = self
+            .check(text)
+            .iter()
+            .map(|m| diagnostic(text, m))
+            .collect();
+        diagnostics_notification(uri.clone(), diagnostics)
+    }
+
+    fn check(&self, text: &str) -> Vec<Misspelling> {
+        self.sym
+            .check_text_with(text, self.options.clone())
+            .collect()
+    }
+
+    /// Quick fixes for the `symspellrs` diagnostics in the request context.
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let mut actions = Vec::new();
+        for diag in &params.context.diagnostics {
+            if diag.source.as_deref() != Some(SOURCE) {
+                continue;
+            }
+            let Some(data) = &diag.data else { continue };
+            let token = data["token"].as_str().unwrap_or_default();
+            let suggestions = data["suggestions"].as_array().cloned().unwrap_or_default();
+            for (i, term) in suggestions.iter().filter_map(Value::as_str).enumerate() {
+                let edit = TextEdit::new(diag.range, term.to_string());
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Change to '{}'", term),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diag.clone()]),
+                    edit: Some(WorkspaceEdit::new(HashMap::from([(
+                        uri.clone(),
+                        vec![edit],
+                    )]))),
+                    is_preferred: Some(i == 0),
+                    ..Default::default()
+                }));
+            }
+            if self.workspace_dictionary.is_some() && !token.is_empty() {
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Add '{}' to workspace dictionary", token),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diag.clone()]),
+                    command: Some(Command::new(
+                        format!("Add '{}' to workspace dictionary", token),
+                        ADD_TO_DICTIONARY.to_string(),
+                        Some(vec![json!(token)]),
+                    )),
+                    ..Default::default()
+                }));
+            }
+        }
+        actions
+    }
+
+    fn execute_command(
+        &mut self,
+        id: lsp_server::RequestId,
+        params: ExecuteCommandParams,
+    ) -> Vec<Message> {
+        if params.command != ADD_TO_DICTIONARY {
+            let message = format!("unknown command: {}", params.command);
+            return vec![Message::Response(Response::new_err(
+                id,
+                ErrorCode::InvalidParams as i32,
+                message,
+            ))];
+        }
+        let Some(word) = params.arguments.first().and_then(Value::as_str) else {
+            let message = format!("{} expects a word", ADD_TO_DICTIONARY);
+            return vec![Message::Response(Response::new_err(
+                id,
+                ErrorCode::InvalidParams as i32,
+                message,
+            ))];
+        };
+        if let Err(e) = self.add_to_dictionary(word) {
+            return vec![Message::Response(Response::new_err(
+                id,
+                ErrorCode::RequestFailed as i32,
+                e.to_string(),
+            ))];
+        }
+        let mut messages = vec![Message::Response(Response::new_ok(id, Value::Null))];
+        let mut uris: Vec<&Url> = self.documents.keys().collect();
+        uris.sort();
+        messages.extend(uris.into_iter().map(|uri| self.publish(uri)));
+        messages
+    }
+
+    /// Append `word` to the workspace dictionary file and learn it.
+    fn add_to_dictionary(&mut self, word: &str) -> io::Result<()> {
+        let path = self.workspace_dictionary.as_ref().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no workspace folder is open")
+        })?;
+        let word = word.trim().to_lowercase();
+        if word.is_empty() || word.contains(char::is_whitespace) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("not a single word: {:?}", word),
+            ));
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Keep one word per line even if the file lacks a trailing newline.
+        let needs_newline = fs::read(path)?.last().is_some_and(|b| *b != b'\n');
+        if needs_newline {
+            writeln!(file)?;
+        }
+        writeln!(file, "{}", word)?;
+        self.sym.load_iter([(word, 1usize)]);
+        Ok(())
+    }
+}
+
+fn invalid_params(id: lsp_server::RequestId, e: impl std::fmt::Debug) -> Response {
+    Response::new_err(id, ErrorCode::InvalidParams as i32, format!("{:?}", e))
+}
+
+fn diagnostics_notification(uri: Url, diagnostics: Vec<Diagnostic>) -> Message {
+    Message::Notification(Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        PublishDiagnosticsParams::new(uri, diagnostics, None),
+    ))
+}
+
+/// Diagnostic for `m`, carrying its token and suggestions in `data` for code actions.
+fn diagnostic(text: &str, m: &Misspelling) -> Diagnostic {
+    let terms: Vec<&str> = m.suggestions.iter().map(|s| s.term.as_str()).collect();
+    let mut message = format!("Unknown word '{}'", m.token);
+    if !terms.is_empty() {
+        message.push_str(&format!(": did you mean {}?", terms.join(", ")));
+    }
+    Diagnostic {
+        range: range(text, m),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(CODE.to_string())),
+        source: Some(SOURCE.to_string()),
+        message,
+        data: Some(json!({ "token": m.token, "suggestions": terms })),
+        ..Default::default()
+    }
+}
+
+/// LSP range of `m`: zero-based line and UTF-16 columns. Uses the line the
+/// checker already counted and only scans back to the start of that line,
+/// so a document is not rescanned for every diagnostic.
+fn range(text: &str, m: &Misspelling) -> Range {
+    let line = (m.line - 1) as u32;
+    let before = &text[..m.byte_range.start];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let start = before[line_start..].encode_utf16().count();
+    let end = start + m.token.encode_utf16().count();
+    Range::new(
+        Position::new(line, start as u32),
+        Position::new(line, end as u32),
+    )
+}
+
+/// Workspace root from the first workspace folder, or the legacy root URI.
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = params.root_uri.as_ref();
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(root_uri)
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(Server::capabilities())?;
+    let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+    let mut server = Server::new(&args, workspace_root(&params).as_deref())?;
+
+    for msg in &connection.receiver {
+        let replies = match msg {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    break;
+                }
+                server.handle_request(req)
+            }
+            Message::Notification(not) => server.handle_notification(not),
+            Message::Response(_) => Vec::new(),
+        };
+        for reply in replies {
+            connection.sender.send(reply)?;
+        }
+    }
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(None) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Some(args)) => match run(args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("symspellrs-lsp: {}", e);
+                ExitCode::from(2)
+            }
+        },
+        Err(e) => {
+            eprintln!("symspellrs-lsp: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{CodeActionContext, TextDocumentIdentifier, TextDocumentItem};
+
+    fn new_server(root: Option<&Path>) -> Server {
+        let args = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            ..Args::default()
+        };
+        Server::new(&args, root).unwrap()
+    }
+
+    fn open(server: &mut Server, uri: &Url, text: &str) -> Vec<Diagnostic> {
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "text".into(), 1, text.into()),
+        };
+        let not = Notification::new(DidOpenTextDocument::METHOD.to_string(), params);
+        published(&server.handle_notification(not)[0])
+    }
+
+    fn published(msg: &Message) -> Vec<Diagnostic> {
+        let Message::Notification(not) = msg else {
+            panic!("expected a notification, got {:?}", msg);
+        };
+        let params: PublishDiagnosticsParams = serde_json::from_value(not.params.clone()).unwrap();
+        params.diagnostics
+    }
+
+    #[test]
+    fn test_diagnostics_and_code_actions() {
+        let mut server = new_server(None);
+        let uri = Url::parse("file:///notes.txt").unwrap();
+        // "😀" is two UTF-16 code units.
+        let diags = open(&mut server, &uri, "hello\n😀 wrld");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].range,
+            Range::new(Position::new(1, 3), Position::new(1, 7))
+        );
+        assert_eq!(diags[0].message, "Unknown word 'wrld': did you mean world?");
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range: diags[0].range,
+            context: CodeActionContext {
+                diagnostics: diags.clone(),
+                ..Default::default()
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let actions = server.code_actions(&params);
+        // No workspace root, so only the replacement is offered.
+        assert_eq!(actions.len(), 1);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        assert_eq!(action.title, "Change to 'world'");
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].new_text, "world");
+        assert_eq!(edits[0].range, diags[0].range);
+    }
+
+    #[test]
+    fn test_add_to_workspace_dictionary() {
+        let root = std::env::temp_dir().join(format!("symspellrs-lsp-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let dict = root.join(".symspellrs-dictionary.txt");
+        fs::write(&dict, "symspellrs").unwrap();
+
+        let mut server = new_server(Some(&root));
+        let uri = Url::from_file_path(root.join("a.md")).unwrap();
+        let diags = open(&mut server, &uri, "symspellrs frobnicate");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].data.as_ref().unwrap()["token"], "frobnicate");
+
+        let params = ExecuteCommandParams {
+            command: ADD_TO_DICTIONARY.to_string(),
+            arguments: vec![json!("Frobnicate")],
+            work_done_progress_params: Default::default(),
+        };
+        let req = Request::new(1.into(), ExecuteCommand::METHOD.to_string(), params);
+        let replies = server.handle_request(req);
+        assert!(matches!(&replies[0], Message::Response(r) if r.error.is_none()));
+        assert!(published(&replies[1]).is_empty());
+        assert_eq!(
+            fs::read_to_string(&dict).unwrap(),
+            "symspellrs\nfrobnicate\n"
+        );
+
+        // A fresh server picks the word up from the file.
+        let mut restarted = new_server(Some(&root));
+        assert!(open(&mut restarted, &uri, "frobnicate").is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = parse_args(
+            ["--stdio", "-d", "w.txt", "--workspace-dictionary=.words"]
+                .iter()
+                .map(|s| s.to_string()),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(parsed.dictionaries, ["w.txt"]);
+        assert_eq!(parsed.workspace_dictionary, ".words");
+        assert!(parse_args(["--help".to_string()]).unwrap().is_none());
+        assert!(parse_args(Vec::new()).is_err());
+    }
+}
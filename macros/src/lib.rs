@@ -9,7 +9,7 @@ use syn::parse::{Parse, ParseStream};
 use syn::{Expr, Ident, LitStr, Token};
 
 /// Macro input representation:
-/// include_dictionary!("path/to/file.txt", max_distance = 2, lowercase = true, has_freq = false, precompute = true, max_deletes = 100000)
+/// include_dictionary!("path/to/file.txt", max_distance = 2, lowercase = true, has_freq = false, precompute = true, max_deletes = 100000, exclude = "path/to/blocked.txt", keep_excluded = false, corrections = "path/to/corrections.txt")
 struct IncludeDictionaryArgs {
     path: LitStr,
     assignments: Vec<(Ident, Expr)>,
@@ -49,12 +49,27 @@ impl Parse for IncludeDictionaryArgs {
 ///  - DICT_PHF: ::phf::Map<&'static str, usize> (word -> freq)
 ///  - DELETES_PHF: ::phf::Map<&'static str, &'static [&'static str]> (deletion -> [words])
 ///
+/// together with `MIN_WORD_LEN` / `MAX_WORD_LEN` constants (shortest and longest word
+/// in characters) passed to `EmbeddedSymSpell::with_word_lengths`, and `WORDS_BY_LEN`
+/// (words grouped by length) passed to `EmbeddedSymSpell::with_length_buckets`.
+///
 /// If `precompute = false` the macro will only emit DICT_PHF and will construct a
 /// runtime `SymSpell` by loading the PHF entries into `SymSpell::load_iter(...)`.
 ///
 /// There is a guard `max_deletes` that prevents emitting enormous deletion indexes; if the
 /// estimated total number of deletion entries exceeds `max_deletes` the macro will abort
 /// with a helpful message (suggest increasing `max_deletes` or setting `precompute = false`).
+///
+/// `exclude = "path"` names a blocklist file (one word per line, `#` comments, lower-cased
+/// with `lowercase`) of words that must never be suggested. By default they are dropped from
+/// the dictionary at compile time. With `keep_excluded = true` they stay in the dictionary as
+/// valid words and the macro emits `EXCLUDED` (the blocked words), passed to `with_blocklist`
+/// as a `Blocklist` that is `recognized`.
+///
+/// `corrections = "path"` names a correction table (`misspelling correction` per line, the
+/// correction being the rest of the line, lower-cased with `lowercase`). The macro emits
+/// `CORRECTIONS` (`(misspelling, correction)` pairs) and passes them to `with_corrections` as a
+/// `CorrectionTable`, consulted before the deletion index.
 #[proc_macro]
 pub fn include_dictionary(input: TokenStream) -> TokenStream {
     // Parse macro arguments
@@ -66,6 +81,9 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
     let mut has_freq: bool = false;
     let mut precompute: bool = true;
     let mut max_deletes: usize = 100_000;
+    let mut exclude: Option<String> = None;
+    let mut keep_excluded: bool = false;
+    let mut corrections: Option<String> = None;
 
     // Interpret assignments
     for (ident, expr) in args.assignments.iter() {
@@ -120,6 +138,33 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
                 },
                 _ => panic!("max_deletes must be an integer literal expression"),
             },
+            "exclude" => match expr {
+                Expr::Lit(el) => match &el.lit {
+                    syn::Lit::Str(ls) => {
+                        exclude = Some(ls.value());
+                    }
+                    _ => panic!("exclude must be a string literal"),
+                },
+                _ => panic!("exclude must be a string literal expression"),
+            },
+            "keep_excluded" => match expr {
+                Expr::Lit(el) => match &el.lit {
+                    syn::Lit::Bool(lb) => {
+                        keep_excluded = lb.value;
+                    }
+                    _ => panic!("keep_excluded must be a boolean literal"),
+                },
+                _ => panic!("keep_excluded must be a boolean literal expression"),
+            },
+            "corrections" => match expr {
+                Expr::Lit(el) => match &el.lit {
+                    syn::Lit::Str(ls) => {
+                        corrections = Some(ls.value());
+                    }
+                    _ => panic!("corrections must be a string literal"),
+                },
+                _ => panic!("corrections must be a string literal expression"),
+            },
             _ => panic!("Unknown argument to include_dictionary: {}", name),
         }
     }
@@ -188,6 +233,59 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
         *dict.entry(word.clone()).or_insert(0) += freq;
     }
 
+    // Blocked words are either dropped here or kept and attached as a blocklist.
+    let excluded: Vec<String> = match &exclude {
+        Some(relative) => read_word_list(&Path::new(&manifest_dir).join(relative), lowercase),
+        None => Vec::new(),
+    };
+    if !keep_excluded {
+        for word in &excluded {
+            dict.remove(word);
+        }
+    }
+    let excluded_static = if keep_excluded && exclude.is_some() {
+        let wl = excluded
+            .iter()
+            .map(|w| syn::LitStr::new(w, Span::call_site()));
+        quote! {
+            static EXCLUDED: &[&str] = &[#( #wl ),*];
+        }
+    } else {
+        quote! {}
+    };
+    // Known misspellings, consulted before the deletion index.
+    let (corrections_static, with_corrections) = match &corrections {
+        Some(relative) => {
+            let entries =
+                read_correction_table(&Path::new(&manifest_dir).join(relative), lowercase);
+            let pairs = entries.iter().map(|(m, c)| {
+                let m = syn::LitStr::new(m, Span::call_site());
+                let c = syn::LitStr::new(c, Span::call_site());
+                quote! { (#m, #c) }
+            });
+            (
+                quote! {
+                    static CORRECTIONS: &[(&str, &str)] = &[#( #pairs ),*];
+                },
+                quote! {
+                    .with_corrections(::symspellrs::CorrectionTable::from_iter(
+                        CORRECTIONS.iter().copied(),
+                    ))
+                },
+            )
+        }
+        None => (quote! {}, quote! {}),
+    };
+    let with_blocklist = if keep_excluded && exclude.is_some() {
+        quote! {
+            .with_blocklist(
+                ::symspellrs::Blocklist::from_iter(EXCLUDED.iter().copied()).with_recognized(true),
+            )
+        }
+    } else {
+        quote! {}
+    };
+
     if precompute {
         // Precompute deletion variants for each word and populate deletes_map.
         // Use the same deletion generation rules as SymSpell implementation.
@@ -208,9 +306,11 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
                     if s.is_empty() {
                         continue;
                     }
-                    for i in 0..s.len() {
+                    // Remove one character (not byte) at a time so multi-byte
+                    // words yield valid deletions, matching `SymSpell`.
+                    for (i, c) in s.char_indices() {
                         let mut t = s.clone();
-                        t.remove(i);
+                        t.replace_range(i..i + c.len_utf8(), "");
                         if generated.insert(t.clone()) {
                             next.insert(t);
                         }
@@ -254,6 +354,41 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
         // max_distance literal
         let max_distance_lit = syn::LitInt::new(&max_distance.to_string(), Span::call_site());
 
+        // Shortest / longest word in characters, used by lookup to reject
+        // queries that no word is within reach of.
+        let lengths = dict.keys().map(|w| w.chars().count());
+        let word_lengths = match (lengths.clone().min(), lengths.max()) {
+            (Some(min), Some(max)) => {
+                let min_lit = syn::LitInt::new(&format!("{}usize", min), Span::call_site());
+                let max_lit = syn::LitInt::new(&format!("{}usize", max), Span::call_site());
+                quote! {
+                    const MIN_WORD_LEN: usize = #min_lit;
+                    const MAX_WORD_LEN: usize = #max_lit;
+                }
+            }
+            _ => quote! {},
+        };
+        let with_word_lengths = if dict.is_empty() {
+            quote! {}
+        } else {
+            quote! { .with_word_lengths(MIN_WORD_LEN, MAX_WORD_LEN) }
+        };
+
+        // Words grouped by length in characters (`WORDS_BY_LEN[n]` holds the words of
+        // length `n`) for `Fallback::LengthBucketed`.
+        let mut buckets: Vec<Vec<syn::LitStr>> = Vec::new();
+        for word in dict.keys() {
+            let len = word.chars().count();
+            if buckets.len() <= len {
+                buckets.resize_with(len + 1, Vec::new);
+            }
+            buckets[len].push(syn::LitStr::new(word, Span::call_site()));
+        }
+        let buckets_quote_iter = buckets.iter().map(|words| {
+            let wl = words.iter();
+            quote! { &[#( #wl ),*] }
+        });
+
         // Build quoted entries for dict and deletes
         let dict_quote_iter = dict_entries_tokens.iter().map(|(k, v)| {
             quote! {
@@ -280,8 +415,22 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
                     #(#deletes_quote_iter, )*
                 };
 
+                static WORDS_BY_LEN: &[&[&str]] = &[
+                    #(#buckets_quote_iter, )*
+                ];
+
+                #word_lengths
+
+                #excluded_static
+
+                #corrections_static
+
                 // Construct and return an EmbeddedSymSpell referencing the statics
                 ::symspellrs::EmbeddedSymSpell::from_phf(#max_distance_lit, &DICT_PHF, &DELETES_PHF)
+                    .with_length_buckets(WORDS_BY_LEN)
+                    #with_word_lengths
+                    #with_blocklist
+                    #with_corrections
             }
         };
 
@@ -309,8 +458,14 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
                     #(#dict_quote_iter, )*
                 };
 
+                #excluded_static
+
+                #corrections_static
+
                 // Build SymSpell at runtime by loading PHF entries
-                let mut sym = ::symspellrs::SymSpell::new(#max_distance_lit);
+                let mut sym = ::symspellrs::SymSpell::new(#max_distance_lit)
+                    #with_blocklist
+                    #with_corrections;
                 sym.load_iter(DICT_PHF.entries().map(|(k, v)| (k.to_string(), *v)));
                 sym
             }
@@ -319,3 +474,145 @@ pub fn include_dictionary(input: TokenStream) -> TokenStream {
         TokenStream::from(expanded)
     }
 }
+
+/// include_bigram_dictionary!("path/to/bigrams.txt", lowercase = true)
+/// Reads a bigram frequency file at compile time (one `first second count` entry per line,
+/// blank lines and `#` comments skipped, counts of repeated pairs summed) and emits
+/// `BIGRAMS_PHF: ::phf::Map<&'static str, usize>` keyed by `"first second"`, returning
+/// `BigramDictionary::from_phf(&BIGRAMS_PHF)`.
+#[proc_macro]
+pub fn include_bigram_dictionary(input: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(input as IncludeDictionaryArgs);
+
+    let mut lowercase: bool = false;
+    for (ident, expr) in args.assignments.iter() {
+        let name = ident.to_string();
+        match name.as_str() {
+            "lowercase" => match expr {
+                Expr::Lit(el) => match &el.lit {
+                    syn::Lit::Bool(lb) => {
+                        lowercase = lb.value;
+                    }
+                    _ => panic!("lowercase must be a boolean literal"),
+                },
+                _ => panic!("lowercase must be a boolean literal expression"),
+            },
+            _ => panic!("Unknown argument to include_bigram_dictionary: {}", name),
+        }
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .expect("CARGO_MANIFEST_DIR environment variable not set");
+    let file_path = Path::new(&manifest_dir).join(args.path.value());
+    let text = std::fs::read_to_string(&file_path).unwrap_or_else(|e| {
+        panic!(
+            "include_bigram_dictionary!: failed to open bigram file '{}': {}",
+            file_path.display(),
+            e
+        )
+    });
+
+    // "first second" -> count (BTreeMap for deterministic order)
+    let mut pairs: BTreeMap<String, usize> = BTreeMap::new();
+    for (lineno, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let mut parts = trimmed.split_whitespace();
+        let (Some(first), Some(second), Some(count)) = (parts.next(), parts.next(), parts.next())
+        else {
+            panic!(
+                "include_bigram_dictionary!: expected `first second count` on line {}: {}",
+                lineno + 1,
+                trimmed
+            );
+        };
+        let count = count.parse::<usize>().unwrap_or_else(|_| {
+            panic!(
+                "include_bigram_dictionary!: invalid count on line {}: {}",
+                lineno + 1,
+                trimmed
+            )
+        });
+        let mut key = format!("{} {}", first, second);
+        if lowercase {
+            key = key.to_lowercase();
+        }
+        *pairs.entry(key).or_insert(0) += count;
+    }
+
+    let entries = pairs.iter().map(|(k, v)| {
+        let key = syn::LitStr::new(k, Span::call_site());
+        let val = syn::LitInt::new(&v.to_string(), Span::call_site());
+        quote! { #key => #val }
+    });
+    let expanded = quote! {
+        {
+            static BIGRAMS_PHF: ::phf::Map<&'static str, usize> = ::phf::phf_map! {
+                #(#entries, )*
+            };
+            ::symspellrs::BigramDictionary::from_phf(&BIGRAMS_PHF)
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Words of a blocklist file (one per line, blank lines and `#` comments skipped).
+fn read_word_list(path: &Path, lowercase: bool) -> Vec<String> {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "include_dictionary!: failed to open exclude file '{}': {}",
+            path.display(),
+            e
+        )
+    });
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|word| {
+            if lowercase {
+                word.to_lowercase()
+            } else {
+                word.to_string()
+            }
+        })
+        .collect()
+}
+
+/// `(misspelling, correction)` entries of a correction table file: the misspelling,
+/// whitespace, then the correction (the rest of the line).
+fn read_correction_table(path: &Path, lowercase: bool) -> Vec<(String, String)> {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "include_dictionary!: failed to open corrections file '{}': {}",
+            path.display(),
+            e
+        )
+    });
+    let case = |s: &str| {
+        if lowercase {
+            s.to_lowercase()
+        } else {
+            s.to_string()
+        }
+    };
+    let mut entries = Vec::new();
+    for (lineno, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (misspelling, correction) =
+            trimmed.split_once(char::is_whitespace).unwrap_or_else(|| {
+                panic!(
+                    "include_dictionary!: expected correction on line {} of {}: {}",
+                    lineno + 1,
+                    path.display(),
+                    trimmed
+                )
+            });
+        entries.push((case(misspelling), case(correction.trim())));
+    }
+    entries
+}
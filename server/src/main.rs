@@ -0,0 +1,527 @@
+/*!
+symspellrs HTTP suggestion service
+
+Serves a loaded dictionary as JSON over HTTP for services not written in Rust:
+
+    GET  /health                                -> {"status": "ok", "words": N, "max_distance": M}
+    GET  /lookup?term=helo&verbosity=closest    -> {"term": "helo", "suggestions": [...]}
+    GET  /compound?text=whereis+th+elove        -> {"text": ..., "suggestion": {...}}
+    GET  /segment?text=thequickbrownfox         -> {"text": ..., "segmented": ..., "corrected": ...}
+
+Every endpoint except `/health` also accepts a `POST` with a JSON object
+holding the same parameters, or a batch: `{"terms": [...]}` for `/lookup`,
+`{"texts": [...]}` for the others, answered with `{"results": [...]}`.
+`max_distance` is optional everywhere and capped at the dictionary's.
+Suggestions are `{"term", "distance", "frequency"}`; errors are
+`{"error": "..."}` with a 4xx status.
+
+Compound correction and segmentation cost far more than a lookup, so their
+texts are limited to `MAX_TEXT` bytes per request (413 beyond), and query
+strings to `MAX_QUERY` bytes (414 beyond).
+*/
+
+use std::error::Error;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Map, Value};
+use symspellrs::{LoadOptions, Segmentation, Suggestion, SymSpell, Verbosity};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const USAGE: &str = "\
+Usage: symspellrs-server --dictionary <FILE> [OPTIONS]
+
+Serve spelling suggestions as JSON over HTTP.
+
+Options:
+  -d, --dictionary <FILE>   Dictionary file, one word per line (repeatable)
+      --freq                Dictionary lines are `word frequency`
+      --lowercase           Lower-case dictionary words
+  -m, --max-distance <N>    Maximum edit distance of the index [default: 2]
+  -b, --bind <ADDR>         Address to listen on [default: 127.0.0.1:8080]
+  -t, --threads <N>         Worker threads [default: 4]
+  -h, --help                Print this help
+";
+
+/// Largest accepted request body.
+const MAX_BODY: u64 = 1 << 20;
+
+/// Largest total length, in bytes, of the texts of a `/compound` or
+/// `/segment` request.
+const MAX_TEXT: usize = 4 << 10;
+
+/// Largest accepted query string.
+const MAX_QUERY: usize = 16 << 10;
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Args {
+    dictionaries: Vec<String>,
+    has_freq: bool,
+    lowercase: bool,
+    max_distance: u8,
+    bind: String,
+    threads: usize,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            dictionaries: Vec::new(),
+            has_freq: false,
+            lowercase: false,
+            max_distance: 2,
+            bind: "127.0.0.1:8080".to_string(),
+            threads: 4,
+        }
+    }
+}
+
+/// `None` when help was requested.
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline {
+                Some(v) => Ok(v.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name)),
+            }
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--dictionary" => parsed.dictionaries.push(value(&flag)?),
+            "--freq" => parsed.has_freq = true,
+            "--lowercase" => parsed.lowercase = true,
+            "-m" | "--max-distance" => {
+                let v = value(&flag)?;
+                parsed.max_distance = v
+                    .parse()
+                    .map_err(|_| format!("invalid max distance: {}", v))?;
+            }
+            "-b" | "--bind" => parsed.bind = value(&flag)?,
+            "-t" | "--threads" => {
+                let v = value(&flag)?;
+                parsed.threads = v
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("invalid thread count: {}", v))?;
+            }
+            _ => return Err(format!("unknown option: {}", arg)),
+        }
+    }
+    if parsed.dictionaries.is_empty() {
+        return Err("missing --dictionary".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+/// An error response: status code and message.
+type Failure = (u16, String);
+
+fn bad_request(message: impl Into<String>) -> Failure {
+    (400, message.into())
+}
+
+/// The dictionary behind the endpoints.
+struct Service {
+    sym: SymSpell,
+}
+
+impl Service {
+    fn new(args: &Args) -> io::Result<Self> {
+        let load = LoadOptions::new()
+            .with_freq(args.has_freq)
+            .with_lowercase(args.lowercase);
+        let mut sym = SymSpell::new(args.max_distance);
+        for path in &args.dictionaries {
+            sym.load_file(path, &load)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+        }
+        Ok(Self { sym })
+    }
+
+    /// Route a request; `url` includes the query string.
+    fn handle(&self, method: &str, url: &str, body: &str) -> (u16, Value) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let result = match (method, path) {
+            _ if query.len() > MAX_QUERY => Err((414, "query string too long".to_string())),
+            ("GET", "/health") => Ok(json!({
+                "status": "ok",
+                "words": self.sym.len(),
+                "max_distance": self.sym.max_distance(),
+            })),
+            (_, "/health") => Err((405, format!("{} not allowed on {}", method, path))),
+            (_, "/lookup" | "/compound" | "/segment") => {
+                params(method, query, body).and_then(|params| self.endpoint(path, &params))
+            }
+            _ => Err((404, format!("no such endpoint: {}", path))),
+        };
+        match result {
+            Ok(value) => (200, value),
+            Err((status, message)) => (status, json!({ "error": message })),
+        }
+    }
+
+    fn endpoint(&self, path: &str, params: &Map<String, Value>) -> Result<Value, Failure> {
+        let max_distance = match params.get("max_distance") {
+            None => self.sym.max_distance(),
+            Some(v) => number(v)
+                .and_then(|n| u8::try_from(n).ok())
+                .ok_or_else(|| bad_request("max_distance must be a small non-negative integer"))?
+                .min(self.sym.max_distance()),
+        };
+        let (single, batch) = if path == "/lookup" {
+            ("term", "terms")
+        } else {
+            ("text", "texts")
+        };
+
+        if path == "/lookup" {
+            let verbosity = match params.get("verbosity").map(|v| v.as_str()) {
+                None => Verbosity::Closest,
+                Some(Some("top")) => Verbosity::Top,
+                Some(Some("closest")) => Verbosity::Closest,
+                Some(Some("all")) => Verbosity::All,
+                Some(_) => return Err(bad_request("verbosity must be top, closest or all")),
+            };
+            let lookup = |terms: &[String]| -> Vec<Value> {
+                let results = self.sym.lookup_batch(terms, max_distance, verbosity);
+                terms
+                    .iter()
+                    .zip(results)
+                    .map(|(term, suggestions)| {
+                        json!({
+                            "term": term,
+                            "suggestions": suggestions.iter().map(suggestion_json).collect::<Vec<_>>(),
+                        })
+                    })
+                    .collect()
+            };
+            return match inputs(params, single, batch)? {
+                Inputs::Single(term) => Ok(lookup(&[term]).remove(0)),
+                Inputs::Batch(terms) => Ok(json!({ "results": lookup(&terms) })),
+            };
+        }
+
+        let correct = |text: &str| -> Value {
+            if path == "/compound" {
+                let suggestion = self.sym.lookup_compound(text, max_distance);
+                json!({ "text": text, "suggestion": suggestion_json(&suggestion) })
+            } else {
+                segmentation_json(text, &self.sym.word_segmentation(text, max_distance))
+            }
+        };
+        let texts = inputs(params, single, batch)?;
+        let total = match &texts {
+            Inputs::Single(text) => text.len(),
+            Inputs::Batch(texts) => texts.iter().map(String::len).sum(),
+        };
+        if total > MAX_TEXT {
+            return Err((413, format!("text too long (at most {} bytes)", MAX_TEXT)));
+        }
+        match texts {
+            Inputs::Single(text) => Ok(correct(&text)),
+            Inputs::Batch(texts) => Ok(json!({
+                "results": texts.iter().map(|t| correct(t)).collect::<Vec<_>>(),
+            })),
+        }
+    }
+}
+
+/// Request parameters: the query string for `GET`, the JSON body for `POST`.
+fn params(method: &str, query: &str, body: &str) -> Result<Map<String, Value>, Failure> {
+    match method {
+        "GET" => Ok(query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key), Value::String(percent_decode(value)))
+            })
+            .collect()),
+        "POST" => match serde_json::from_str(body) {
+            Ok(Value::Object(map)) => Ok(map),
+            Ok(_) => Err(bad_request("request body must be a JSON object")),
+            Err(e) => Err(bad_request(format!("invalid JSON: {}", e))),
+        },
+        _ => Err((405, format!("{} not allowed", method))),
+    }
+}
+
+enum Inputs {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+/// The `single` string parameter or the `batch` array of strings.
+fn inputs(params: &Map<String, Value>, single: &str, batch: &str) -> Result<Inputs, Failure> {
+    if let Some(list) = params.get(batch) {
+        let items = list
+            .as_array()
+            .and_then(|items| {
+                items
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| bad_request(format!("{} must be an array of strings", batch)))?;
+        return Ok(Inputs::Batch(items));
+    }
+    match params.get(single) {
+        Some(Value::String(s)) => Ok(Inputs::Single(s.clone())),
+        Some(_) => Err(bad_request(format!("{} must be a string", single))),
+        None => Err(bad_request(format!("missing {} or {}", single, batch))),
+    }
+}
+
+/// A JSON number, or a string holding one (query parameters).
+fn number(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn suggestion_json(s: &Suggestion) -> Value {
+    json!({ "term": s.term, "distance": s.distance, "frequency": s.frequency })
+}
+
+fn segmentation_json(text: &str, s: &Segmentation) -> Value {
+    json!({
+        "text": text,
+        "segmented": s.segmented,
+        "corrected": s.corrected,
+        "distance_sum": s.distance_sum,
+        "log_probability": s.log_probability,
+    })
+}
+
+/// Decode `application/x-www-form-urlencoded` text (`+` is a space).
+/// Malformed escapes are kept literally.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Answer requests until the server is unblocked.
+fn serve(server: &Server, service: &Service) {
+    for mut request in server.incoming_requests() {
+        let (status, value) = match read_body(&mut request) {
+            Ok(body) => service.handle(request.method().as_str(), request.url(), &body),
+            Err((status, message)) => (status, json!({ "error": message })),
+        };
+        let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("static header is valid");
+        let response = Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        // The client may have gone away; nothing useful to do about it.
+        let _ = request.respond(response);
+    }
+}
+
+fn read_body(request: &mut Request) -> Result<String, Failure> {
+    if *request.method() != Method::Post {
+        return Ok(String::new());
+    }
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > MAX_BODY)
+    {
+        return Err((413, "request body too large".to_string()));
+    }
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| bad_request(format!("unreadable body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY {
+        return Err((413, "request body too large".to_string()));
+    }
+    Ok(body)
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let service = Arc::new(Service::new(&args)?);
+    let server = Arc::new(Server::http(&args.bind)?);
+    eprintln!(
+        "symspellrs-server: {} words, listening on http://{}",
+        service.sym.len(),
+        args.bind
+    );
+    let workers: Vec<_> = (0..args.threads)
+        .map(|_| {
+            let (server, service) = (Arc::clone(&server), Arc::clone(&service));
+            thread::spawn(move || serve(&server, &service))
+        })
+        .collect();
+    for worker in workers {
+        worker.join().map_err(|_| "worker thread panicked")?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(None) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Some(args)) => match run(args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("symspellrs-server: {}", e);
+                ExitCode::from(2)
+            }
+        },
+        Err(e) => {
+            eprintln!("symspellrs-server: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    fn service() -> Service {
+        let args = Args {
+            dictionaries: vec!["../tests/data/words.txt".to_string()],
+            ..Args::default()
+        };
+        Service::new(&args).unwrap()
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let service = service();
+
+        let (status, health) = service.handle("GET", "/health", "");
+        assert_eq!(status, 200);
+        assert_eq!(health["status"], "ok");
+
+        let (_, lookup) = service.handle("GET", "/lookup?term=wrld&verbosity=top", "");
+        assert_eq!(lookup["suggestions"][0]["term"], "world");
+        assert_eq!(lookup["suggestions"][0]["distance"], 1);
+
+        let (_, batch) = service.handle(
+            "POST",
+            "/lookup",
+            r#"{"terms": ["helo", "wrld"], "max_distance": 1, "verbosity": "top"}"#,
+        );
+        assert_eq!(batch["results"][1]["term"], "wrld");
+        assert_eq!(batch["results"][1]["suggestions"][0]["term"], "world");
+
+        let (_, compound) = service.handle("GET", "/compound?text=hel%20lo+wrld", "");
+        assert_eq!(compound["text"], "hel lo wrld");
+        assert_eq!(compound["suggestion"]["term"], "hello world");
+
+        let (_, segment) = service.handle("POST", "/segment", r#"{"texts": ["helloworld"]}"#);
+        assert_eq!(segment["results"][0]["corrected"], "hello world");
+
+        assert_eq!(service.handle("GET", "/nope", "").0, 404);
+        assert_eq!(service.handle("POST", "/health", "").0, 405);
+        assert_eq!(service.handle("GET", "/lookup", "").0, 400);
+        assert_eq!(service.handle("POST", "/lookup", "[1]").0, 400);
+        assert_eq!(
+            service.handle("GET", "/lookup?term=a&verbosity=x", "").0,
+            400
+        );
+        assert_eq!(
+            service.handle("POST", "/segment", r#"{"texts": [1]}"#).0,
+            400
+        );
+    }
+
+    #[test]
+    fn test_input_limits() {
+        let service = service();
+        let long = "a".repeat(MAX_TEXT + 1);
+        let url = format!("/segment?text={}", long);
+        assert_eq!(service.handle("GET", &url, "").0, 413);
+        let url = format!("/compound?text={}", &long[..MAX_TEXT]);
+        assert_eq!(service.handle("GET", &url, "").0, 200);
+
+        // Batches are limited in total.
+        let half = "a".repeat(MAX_TEXT / 2 + 1);
+        let body = json!({ "texts": [half, half] }).to_string();
+        assert_eq!(service.handle("POST", "/compound", &body).0, 413);
+        // Lookups are cheap and keep the body limit only.
+        let body = json!({ "terms": [long] }).to_string();
+        assert_eq!(service.handle("POST", "/lookup", &body).0, 200);
+
+        let url = format!("/lookup?term={}", "a".repeat(MAX_QUERY));
+        assert_eq!(service.handle("GET", &url, "").0, 414);
+    }
+
+    #[test]
+    fn test_serve_over_localhost() {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let addr = server.server_addr().to_ip().unwrap();
+        let worker = {
+            let server = Arc::clone(&server);
+            thread::spawn(move || serve(&server, &service()))
+        };
+
+        let body = r#"{"term": "aple"}"#;
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /lookup HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("application/json"));
+        let json: Value = serde_json::from_str(response.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(json["suggestions"][0]["term"], "apple");
+
+        server.unblock();
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a+b%20c%C3%A9"), "a b cé");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+}
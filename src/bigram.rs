@@ -0,0 +1,413 @@
+/*!
+bigram module
+
+Context-aware ranking with a bigram frequency dictionary. Single-word
+ranking picks the most frequent candidate, e.g. `"form"` over `"from"` for
+`"frm"` whatever the surrounding words; with bigram counts `"came frm"`
+yields `"from"` and `"the frm"` yields `"form"`.
+
+- `BigramDictionary` holds `(first, second) -> count` pairs, built at runtime
+  (`from_file`, `insert`) or compiled in with `include_bigram_dictionary!`.
+  Files have one `first second count` entry per line, as in SymSpell's
+  bigram dictionaries; blank lines and `#` comments are skipped.
+- `ContextualLookup` wraps any `Lookup` with a `BigramDictionary`.
+  `lookup_context` takes the previous and next words and reorders the
+  candidates at each distance by context, then frequency; `lookup_compound`
+  picks each word's correction in the context of its neighbours.
+
+Candidates are scored with stupid backoff: `count(previous candidate) /
+count(previous)` if the pair is known, else `0.4 * count(candidate) /
+CORPUS_SIZE`, and likewise for the candidate followed by the next word. The
+scores only order candidates; edit distance still comes first, unless the
+wrapped dictionary ranks with a `NoisyChannel`: then the context score is
+added to each `Suggestion::score`, which orders candidates whatever their
+distance.
+*/
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::compound::{self, Segmentation, CORPUS_SIZE};
+use crate::distance::DistanceMetric;
+use crate::loader::LoadOptions;
+use crate::lookup::{apply_verbosity, merge_order, Lookup};
+use crate::noisy::NoisyChannel;
+use crate::symspell::{Suggestion, Verbosity};
+
+/// Weight of the unigram estimate when a bigram is unknown.
+const BACKOFF: f64 = 0.4;
+
+enum Pairs {
+    Owned(HashMap<String, usize>),
+    Embedded(&'static ::phf::Map<&'static str, usize>),
+}
+
+/// Bigram counts, keyed by `"first second"`.
+pub struct BigramDictionary {
+    pairs: Pairs,
+}
+
+impl Default for BigramDictionary {
+    fn default() -> Self {
+        Self {
+            pairs: Pairs::Owned(HashMap::new()),
+        }
+    }
+}
+
+impl BigramDictionary {
+    /// An empty bigram dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a map generated by `include_bigram_dictionary!`, keyed by
+    /// `"first second"`.
+    pub fn from_phf(pairs: &'static ::phf::Map<&'static str, usize>) -> Self {
+        Self {
+            pairs: Pairs::Embedded(pairs),
+        }
+    }
+
+    /// Read `first second count` entries from `reader`. Only
+    /// `options.lowercase` applies; repeated pairs have their counts summed.
+    ///
+    /// Malformed lines are reported as `io::ErrorKind::InvalidData` with their
+    /// line number.
+    pub fn read<R: BufRead>(reader: R, options: &LoadOptions) -> io::Result<Self> {
+        let mut pairs: HashMap<String, usize> = HashMap::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let invalid = |what: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}: {}", lineno + 1, what, trimmed),
+                )
+            };
+            let mut parts = trimmed.split_whitespace();
+            let (Some(first), Some(second)) = (parts.next(), parts.next()) else {
+                return Err(invalid("expected two words"));
+            };
+            let count = parts
+                .next()
+                .ok_or_else(|| invalid("expected count"))?
+                .parse::<usize>()
+                .map_err(|_| invalid("invalid count"))?;
+            let mut key = format!("{} {}", first, second);
+            if options.lowercase {
+                key = key.to_lowercase();
+            }
+            *pairs.entry(key).or_insert(0) += count;
+        }
+        Ok(Self {
+            pairs: Pairs::Owned(pairs),
+        })
+    }
+
+    /// Read a bigram file (see `read`).
+    pub fn from_file<P: AsRef<Path>>(path: P, options: &LoadOptions) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?), options)
+    }
+
+    /// Set the count of `first second`, replacing any previous one. An
+    /// embedded dictionary is copied the first time it is modified.
+    pub fn insert(&mut self, first: &str, second: &str, count: usize) {
+        if let Pairs::Embedded(map) = self.pairs {
+            let owned = map.entries().map(|(k, v)| (k.to_string(), *v)).collect();
+            self.pairs = Pairs::Owned(owned);
+        }
+        if let Pairs::Owned(pairs) = &mut self.pairs {
+            pairs.insert(format!("{} {}", first, second), count);
+        }
+    }
+
+    /// Count of `first` followed by `second`, if known.
+    pub fn frequency(&self, first: &str, second: &str) -> Option<usize> {
+        let key = format!("{} {}", first, second);
+        match &self.pairs {
+            Pairs::Owned(pairs) => pairs.get(&key).copied(),
+            Pairs::Embedded(map) => map.get(key.as_str()).copied(),
+        }
+    }
+
+    /// Number of pairs.
+    pub fn len(&self) -> usize {
+        match &self.pairs {
+            Pairs::Owned(pairs) => pairs.len(),
+            Pairs::Embedded(map) => map.len(),
+        }
+    }
+
+    /// Returns true if there are no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Look up `term` between `previous` and `next` (see `ContextualLookup::lookup_context`).
+pub(crate) fn lookup_context<L: Lookup + ?Sized>(
+    dict: &L,
+    bigrams: &BigramDictionary,
+    term: &str,
+    previous: Option<&str>,
+    next: Option<&str>,
+    max_distance: u8,
+    verbosity: Verbosity,
+) -> Vec<Suggestion> {
+    // Context only reorders candidates at the same distance, so `Top` needs
+    // all of the closest ones. With noisy-channel scoring the best candidate
+    // may be farther away, so every candidate is needed.
+    let candidates = match verbosity {
+        Verbosity::Top | Verbosity::Closest if dict.noisy_channel().is_none() => Verbosity::Closest,
+        _ => Verbosity::All,
+    };
+    let mut suggestions = dict.lookup(term, max_distance, candidates);
+    if previous.is_none() && next.is_none() {
+        apply_verbosity(&mut suggestions, verbosity);
+        return suggestions;
+    }
+    let mut scored: Vec<(f64, Suggestion)> = suggestions
+        .into_iter()
+        .map(|mut s| {
+            let context = context_score(dict, bigrams, &s, previous, next);
+            // Noisy-channel scores take the context's log probability in.
+            s.score = s.score.map(|score| score + context);
+            (context, s)
+        })
+        .collect();
+    // Stable: equal scores keep the dictionary's own order.
+    scored.sort_by(|(ca, a), (cb, b)| {
+        merge_order(a, b)
+            .then(a.distance.cmp(&b.distance))
+            .then(cb.total_cmp(ca))
+    });
+    let mut suggestions: Vec<Suggestion> = scored.into_iter().map(|(_, s)| s).collect();
+    apply_verbosity(&mut suggestions, verbosity);
+    suggestions
+}
+
+/// Log10 stupid-backoff score of `candidate` after `previous` and before `next`.
+fn context_score<L: Lookup + ?Sized>(
+    dict: &L,
+    bigrams: &BigramDictionary,
+    candidate: &Suggestion,
+    previous: Option<&str>,
+    next: Option<&str>,
+) -> f64 {
+    let mut score = 0.0;
+    if let Some(previous) = previous {
+        score += conditional(
+            bigrams.frequency(previous, &candidate.term),
+            dict.frequency(previous),
+            candidate.frequency,
+        )
+        .log10();
+    }
+    if let Some(next) = next {
+        score += conditional(
+            bigrams.frequency(&candidate.term, next),
+            Some(candidate.frequency),
+            dict.frequency(next).unwrap_or(0),
+        )
+        .log10();
+    }
+    score
+}
+
+/// Estimate of P(word | given) from the pair count, backing off to the
+/// unigram probability of `word` when the pair (or `given`) is unknown.
+fn conditional(pair: Option<usize>, given: Option<usize>, word: usize) -> f64 {
+    match (pair, given) {
+        (Some(pair), Some(given)) if pair > 0 && given > 0 => (pair as f64 / given as f64).min(1.0),
+        _ => BACKOFF * word.max(1) as f64 / CORPUS_SIZE,
+    }
+}
+
+/// A `Lookup` that ranks candidates in the context of neighbouring words.
+pub struct ContextualLookup<L> {
+    inner: L,
+    bigrams: BigramDictionary,
+}
+
+impl<L: Lookup> ContextualLookup<L> {
+    /// Rank candidates of `inner` with `bigrams`.
+    pub fn new(inner: L, bigrams: BigramDictionary) -> Self {
+        Self { inner, bigrams }
+    }
+
+    /// The wrapped dictionary.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// The bigram counts.
+    pub fn bigrams(&self) -> &BigramDictionary {
+        &self.bigrams
+    }
+
+    /// Look up `term`, the word between `previous` and `next` (either may be
+    /// `None` at the edges of a sentence). Candidates at the same distance are
+    /// ordered by how well they fit the context, then by frequency.
+    pub fn lookup_context(
+        &self,
+        term: &str,
+        previous: Option<&str>,
+        next: Option<&str>,
+        max_distance: u8,
+        verbosity: Verbosity,
+    ) -> Vec<Suggestion> {
+        lookup_context(
+            &self.inner,
+            &self.bigrams,
+            term,
+            previous,
+            next,
+            max_distance,
+            verbosity,
+        )
+    }
+
+    /// Correct a whole phrase (see `SymSpell::lookup_compound`), choosing each
+    /// word's correction in the context of its neighbours.
+    pub fn lookup_compound(&self, input: &str, max_distance: u8) -> Suggestion {
+        compound::lookup_compound(&self.inner, input, max_distance, Some(&self.bigrams))
+    }
+}
+
+impl<L: Lookup> Lookup for ContextualLookup<L> {
+    /// Lookup without context, as the wrapped dictionary does.
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        self.inner.lookup(term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.inner.max_distance()
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        self.inner.frequency(word)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.inner.contains(word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.inner.word_lengths()
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.inner.distance_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.inner.noisy_channel()
+    }
+
+    fn lookup_compound(&self, input: &str, max_distance: u8) -> Suggestion {
+        ContextualLookup::lookup_compound(self, input, max_distance)
+    }
+
+    fn word_segmentation(&self, input: &str, max_distance: u8) -> Segmentation {
+        self.inner.word_segmentation(input, max_distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::SymSpell;
+
+    fn contextual() -> ContextualLookup<SymSpell> {
+        let sym = SymSpell::from_iter(
+            2,
+            [
+                ("from", 1000usize),
+                ("form", 3000),
+                ("came", 200),
+                ("the", 5000),
+                ("fill", 100),
+                ("in", 4000),
+            ],
+        );
+        let text = "# first second count\ncame from 80\nfrom the 300\nthe form 150\nFill In 20\n";
+        let bigrams =
+            BigramDictionary::read(text.as_bytes(), &LoadOptions::new().with_lowercase(true))
+                .unwrap();
+        ContextualLookup::new(sym, bigrams)
+    }
+
+    #[test]
+    fn test_bigram_dictionary() {
+        let bigrams = contextual().bigrams;
+        assert_eq!(bigrams.len(), 4);
+        assert_eq!(bigrams.frequency("fill", "in"), Some(20));
+        assert_eq!(bigrams.frequency("in", "fill"), None);
+        let err = BigramDictionary::read("came from\n".as_bytes(), &LoadOptions::new())
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("line 1: expected count"));
+    }
+
+    #[test]
+    fn test_lookup_context() {
+        let dict = contextual();
+        let top = |previous, next| {
+            dict.lookup_context("frm", previous, next, 2, Verbosity::Top)[0]
+                .term
+                .clone()
+        };
+        // Without context the more frequent word wins.
+        assert_eq!(top(None, None), "form");
+        assert_eq!(top(Some("came"), None), "from");
+        assert_eq!(top(None, Some("the")), "from");
+        assert_eq!(top(Some("the"), None), "form");
+
+        let closest = dict.lookup_context("frm", Some("came"), None, 2, Verbosity::Closest);
+        let terms: Vec<&str> = closest.iter().map(|s| s.term.as_str()).collect();
+        assert_eq!(terms, ["from", "form"]);
+
+        // Compound lookups use the neighbours too.
+        assert_eq!(
+            dict.lookup_compound("came frm the", 2).term,
+            "came from the"
+        );
+        assert_eq!(
+            Lookup::lookup_compound(&dict, "the frm", 2).term,
+            "the form"
+        );
+        assert_eq!(
+            dict.inner().lookup_compound("came frm the", 2).term,
+            "came form the"
+        );
+    }
+
+    #[test]
+    fn test_lookup_context_noisy_channel() {
+        use crate::noisy::NoisyChannel;
+
+        let ContextualLookup { inner, bigrams } = contextual();
+        let dict = ContextualLookup::new(inner.with_noisy_channel(NoisyChannel::new()), bigrams);
+
+        // "from" is one edit from "frum", "form" two; after "the" the context
+        // lifts the farther word, which a `Closest` query would never see.
+        assert_eq!(
+            dict.lookup_context("frum", None, None, 2, Verbosity::Top)[0].term,
+            "from"
+        );
+        let top = &dict.lookup_context("frum", Some("the"), None, 2, Verbosity::Top)[0];
+        assert_eq!((top.term.as_str(), top.distance), ("form", 2));
+        // The context's log probability is part of the score.
+        let plain = &dict.inner().lookup("frum", 2, Verbosity::All);
+        let form = plain.iter().find(|s| s.term == "form").unwrap();
+        let context = (150.0f64 / 5000.0).log10();
+        assert!((top.score.unwrap() - (form.score.unwrap() + context)).abs() < 1e-9);
+    }
+}
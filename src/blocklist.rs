@@ -0,0 +1,157 @@
+/*!
+blocklist module
+
+A `Blocklist` holds words that must never be returned as suggestions, such as
+profanity present in corpus-derived frequency lists, even when they are the
+closest match. Attach one with `SymSpell::with_blocklist` or
+`EmbeddedSymSpell::with_blocklist`, or filter words out at compile time with
+the `exclude` option of `include_dictionary!`.
+
+By default blocked words are also treated as unknown: `frequency` and
+`contains` ignore them, so text checks report them as misspellings. With
+`with_recognized(true)` they still count as correctly spelled and are only
+kept out of suggestions.
+
+Blocklist files use the `loader` format without frequencies: one word per
+line, blank lines and `#` comments skipped. Words match exactly (lower-case
+them with `LoadOptions::with_lowercase` if the dictionary is lower-case).
+*/
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::loader::{read_dictionary, LoadOptions};
+
+/// Words that are never suggested.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blocklist {
+    words: HashSet<String>,
+    recognized: bool,
+}
+
+impl Blocklist {
+    /// An empty blocklist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a blocklist from `reader`. Only the word of each line is used, so
+    /// a frequency list (with `options.has_freq`) works as well.
+    pub fn read<R: BufRead>(reader: R, options: &LoadOptions) -> io::Result<Self> {
+        let entries = read_dictionary(reader, options)?;
+        Ok(entries.into_iter().map(|(word, _)| word).collect())
+    }
+
+    /// Read a blocklist file (see `read`).
+    pub fn from_file<P: AsRef<Path>>(path: P, options: &LoadOptions) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?), options)
+    }
+
+    /// Whether blocked words still count as correctly spelled (`false` by
+    /// default). Either way they are never suggested.
+    pub fn with_recognized(mut self, recognized: bool) -> Self {
+        self.recognized = recognized;
+        self
+    }
+
+    /// Returns true if blocked words count as correctly spelled.
+    pub fn recognized(&self) -> bool {
+        self.recognized
+    }
+
+    /// Block `word`. Returns false if it was already blocked.
+    pub fn insert<S: Into<String>>(&mut self, word: S) -> bool {
+        self.words.insert(word.into())
+    }
+
+    /// Unblock `word`. Returns true if it was blocked.
+    pub fn remove(&mut self, word: &str) -> bool {
+        self.words.remove(word)
+    }
+
+    /// Returns true if `word` is blocked.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Returns true if `word` is blocked and does not count as a valid word.
+    pub(crate) fn rejects(&self, word: &str) -> bool {
+        !self.recognized && self.contains(word)
+    }
+
+    /// Number of blocked words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if no word is blocked.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Iterate over the blocked words, in no particular order.
+    pub fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.words.iter().map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Blocklist {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut blocklist = Self::new();
+        blocklist.extend(iter);
+        blocklist
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Blocklist {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.words.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::{SymSpell, Verbosity};
+
+    fn sym() -> SymSpell {
+        SymSpell::from_iter(
+            2,
+            [
+                ("duck", 500usize),
+                ("dock", 100),
+                ("deck", 50),
+                ("hello", 10),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_blocklist_hides_suggestions() {
+        let blocklist = Blocklist::read("# never\nduck\n".as_bytes(), &LoadOptions::new()).unwrap();
+        assert!(blocklist.contains("duck") && blocklist.len() == 1);
+
+        let sym = sym().with_blocklist(blocklist.clone());
+        let terms = |sym: &SymSpell, term: &str, verbosity| -> Vec<String> {
+            sym.lookup(term, 2, verbosity)
+                .into_iter()
+                .map(|s| s.term)
+                .collect()
+        };
+        // The most frequent candidate is skipped, not the whole lookup.
+        assert_eq!(terms(&sym, "dack", Verbosity::Closest), ["dock", "deck"]);
+        assert_eq!(terms(&sym, "duck", Verbosity::Top), ["dock"]);
+        // Blocked words are unknown by default...
+        assert_eq!(sym.frequency("duck"), None);
+        assert_eq!(sym.check_text("hello duck").count(), 1);
+
+        // ...or valid but still never suggested.
+        let sym = sym.with_blocklist(blocklist.with_recognized(true));
+        assert_eq!(sym.frequency("duck"), Some(500));
+        assert_eq!(sym.check_text("hello duck").count(), 0);
+        assert_eq!(terms(&sym, "duck", Verbosity::All), ["dock", "deck"]);
+        assert_eq!(sym.blocklist().map(Blocklist::len), Some(1));
+    }
+}
@@ -0,0 +1,364 @@
+/*!
+cache module
+
+`CachedLookup` wraps any `Lookup` (`SymSpell`, `EmbeddedSymSpell`, ...) with a
+bounded least-recently-used cache of lookup results keyed by
+`(term, max_distance, verbosity)`.
+
+Natural text repeats the same words and misspellings constantly, so caching
+whole results skips both deletion enumeration and candidate verification for
+repeated queries. The cache is split into independently locked shards so that
+concurrent readers rarely contend, and the wrapped lookup runs without holding
+any lock.
+*/
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::distance::DistanceMetric;
+use crate::lookup::Lookup;
+use crate::noisy::NoisyChannel;
+use crate::symspell::{Suggestion, Verbosity};
+
+/// Number of shards used once the capacity allows at least one entry each.
+const SHARDS: usize = 16;
+
+/// A `Lookup` with a bounded LRU cache of results in front of it.
+pub struct CachedLookup<L> {
+    inner: L,
+    shards: Box<[Mutex<Lru>]>,
+    hasher: RandomState,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Cache counters returned by `CachedLookup::stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups forwarded to the wrapped dictionary.
+    pub misses: u64,
+    /// Results currently cached.
+    pub entries: usize,
+    /// Maximum number of cached results.
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups answered from the cache (0 before any lookup).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl<L: Lookup> CachedLookup<L> {
+    /// Cache up to `capacity` lookup results for `inner`. A capacity of 0
+    /// disables caching (every lookup is a miss).
+    pub fn new(inner: L, capacity: usize) -> Self {
+        let shards = if capacity >= SHARDS { SHARDS } else { 1 };
+        // Spread the capacity so the shards add up to exactly `capacity`.
+        let shards = (0..shards)
+            .map(|i| {
+                Mutex::new(Lru::new(
+                    capacity / shards + usize::from(i < capacity % shards),
+                ))
+            })
+            .collect();
+        Self {
+            inner,
+            shards,
+            hasher: RandomState::new(),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The wrapped dictionary.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Unwrap the dictionary, dropping the cache.
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    /// Look up suggestions for `term`, answering from the cache when the same
+    /// query was seen recently. `max_distance` is capped by the wrapped
+    /// dictionary's `max_distance` before it is used as part of the key.
+    pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        let key = Key {
+            term: term.to_string(),
+            max_distance: max_distance.min(self.inner.max_distance()),
+            verbosity,
+        };
+        let shard = &self.shards[self.hasher.hash_one(&key) as usize % self.shards.len()];
+        if let Some(hit) = lock(shard).get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return hit;
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let suggestions = self.inner.lookup(term, key.max_distance, verbosity);
+        lock(shard).insert(key, suggestions.clone());
+        suggestions
+    }
+
+    /// Hit/miss counters and current occupancy.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.shards.iter().map(|s| lock(s).map.len()).sum(),
+            capacity: self.capacity,
+        }
+    }
+
+    /// Drop every cached result and reset the counters, e.g. after the
+    /// wrapped dictionary changed.
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            lock(shard).clear();
+        }
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<L: Lookup> Lookup for CachedLookup<L> {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        CachedLookup::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.inner.max_distance()
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        self.inner.frequency(word)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.inner.contains(word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.inner.word_lengths()
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.inner.distance_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.inner.noisy_channel()
+    }
+}
+
+/// Lock a shard, recovering it if a panicking thread poisoned the lock: the
+/// cache holds no invariants a panic in the middle of `lookup` could break.
+fn lock(shard: &Mutex<Lru>) -> std::sync::MutexGuard<'_, Lru> {
+    shard
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Key {
+    term: String,
+    max_distance: u8,
+    verbosity: Verbosity,
+}
+
+/// Index of "no node" in the LRU list.
+const NIL: usize = usize::MAX;
+
+struct Node {
+    key: Key,
+    value: Vec<Suggestion>,
+    prev: usize,
+    next: usize,
+}
+
+/// Fixed-capacity LRU map: a hash map into a doubly linked list of nodes
+/// stored in a `Vec`, most recently used first.
+struct Lru {
+    map: HashMap<Key, usize>,
+    nodes: Vec<Node>,
+    head: usize,
+    tail: usize,
+    capacity: usize,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::new(),
+            nodes: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    fn get(&mut self, key: &Key) -> Option<Vec<Suggestion>> {
+        let idx = *self.map.get(key)?;
+        self.detach(idx);
+        self.push_front(idx);
+        Some(self.nodes[idx].value.clone())
+    }
+
+    fn insert(&mut self, key: Key, value: Vec<Suggestion>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(&idx) = self.map.get(&key) {
+            // Another thread cached the same query while we were looking it up.
+            self.nodes[idx].value = value;
+            self.detach(idx);
+            self.push_front(idx);
+            return;
+        }
+        let idx = if self.nodes.len() < self.capacity {
+            self.nodes.push(Node {
+                key: key.clone(),
+                value,
+                prev: NIL,
+                next: NIL,
+            });
+            self.nodes.len() - 1
+        } else {
+            // Reuse the least recently used node.
+            let idx = self.tail;
+            self.detach(idx);
+            let node = &mut self.nodes[idx];
+            self.map.remove(&node.key);
+            node.key = key.clone();
+            node.value = value;
+            idx
+        };
+        self.map.insert(key, idx);
+        self.push_front(idx);
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    fn detach(&mut self, idx: usize) {
+        let (prev, next) = (self.nodes[idx].prev, self.nodes[idx].next);
+        match prev {
+            NIL => self.head = next,
+            p => self.nodes[p].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            n => self.nodes[n].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, idx: usize) {
+        self.nodes[idx].prev = NIL;
+        self.nodes[idx].next = self.head;
+        match self.head {
+            NIL => self.tail = idx,
+            h => self.nodes[h].prev = idx,
+        }
+        self.head = idx;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::SymSpell;
+
+    fn dictionary() -> SymSpell {
+        let entries = vec![
+            ("hello".to_string(), 100usize),
+            ("help".to_string(), 10usize),
+            ("world".to_string(), 200usize),
+        ];
+        SymSpell::from_iter(2, entries)
+    }
+
+    #[test]
+    fn test_cache_hits_and_misses() {
+        let cached = CachedLookup::new(dictionary(), 8);
+        let first = cached.lookup("helo", 2, Verbosity::Top);
+        assert_eq!(first[0].term, "hello");
+        assert_eq!(cached.lookup("helo", 2, Verbosity::Top), first);
+        // A larger distance than the index supports is the same query.
+        assert_eq!(cached.lookup("helo", 5, Verbosity::Top), first);
+        // Different verbosity is a different key.
+        cached.lookup("helo", 2, Verbosity::All);
+
+        let stats = cached.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 2, 2));
+        assert_eq!(stats.hit_rate(), 0.5);
+
+        cached.clear();
+        assert_eq!(
+            cached.stats(),
+            CacheStats {
+                capacity: 8,
+                ..CacheStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let cached = CachedLookup::new(dictionary(), 2);
+        cached.lookup("helo", 2, Verbosity::Top);
+        cached.lookup("wrld", 2, Verbosity::Top);
+        cached.lookup("helo", 2, Verbosity::Top); // "helo" is now most recent
+        cached.lookup("hlp", 2, Verbosity::Top); // evicts "wrld"
+        assert_eq!(cached.stats().entries, 2);
+
+        cached.lookup("helo", 2, Verbosity::Top);
+        assert_eq!(cached.stats().hits, 2);
+        cached.lookup("wrld", 2, Verbosity::Top);
+        assert_eq!(cached.stats().misses, 4);
+
+        let disabled = CachedLookup::new(dictionary(), 0);
+        disabled.lookup("helo", 2, Verbosity::Top);
+        disabled.lookup("helo", 2, Verbosity::Top);
+        assert_eq!(disabled.stats().misses, 2);
+        assert_eq!(disabled.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_cache_concurrent_readers() {
+        let cached = CachedLookup::new(dictionary(), 64);
+        let terms = ["helo", "wrld", "hlp", "helo", "wold"];
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        for term in terms {
+                            let expected = cached.inner().lookup(term, 2, Verbosity::Closest);
+                            assert_eq!(cached.lookup(term, 2, Verbosity::Closest), expected);
+                        }
+                    }
+                });
+            }
+        });
+        let stats = cached.stats();
+        assert_eq!(stats.hits + stats.misses, 4 * 100 * terms.len() as u64);
+        assert_eq!(stats.entries, 4);
+    }
+}
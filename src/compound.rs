@@ -0,0 +1,360 @@
+/*!
+compound module
+
+Corrections that move word boundaries, after SymSpell's `LookupCompound` and
+`WordSegmentation`:
+
+- `lookup_compound` corrects a whole phrase. Each word is corrected on its
+  own, merged with the previous word (`"hel lo"` -> `"hello"`) or split in two
+  (`"inthe"` -> `"in the"`), whichever is closer and more likely;
+- `word_segmentation` inserts missing spaces into run-together text
+  (`"thequickbrownfox"` -> `"the quick brown fox"`), correcting each segment,
+  with a dynamic program over word probabilities.
+
+Given a `BigramDictionary` (see `ContextualLookup`), `lookup_compound` picks
+each word's correction in the context of the previous corrected word and the
+next input word.
+
+A word's probability is `frequency / CORPUS_SIZE`. Words missing from the
+dictionary get `10 / (CORPUS_SIZE * 10^len)`, so long unknown words are
+unlikely. Distances of split and merged results are measured with
+`OptimalStringAlignment`.
+*/
+
+use crate::bigram::{self, BigramDictionary};
+use crate::distance::{DistanceMetric, OptimalStringAlignment};
+use crate::lookup::Lookup;
+use crate::symspell::{Origin, Suggestion, Verbosity};
+
+/// Number of words the dictionary frequencies are assumed to be counted over
+/// (the size of the Google Books corpus behind SymSpell's English frequency
+/// dictionary).
+pub const CORPUS_SIZE: f64 = 1_024_908_267_229.0;
+
+/// Result of `word_segmentation`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Segmentation {
+    /// The input with spaces inserted, otherwise unchanged.
+    pub segmented: String,
+    /// The segmented input with each segment corrected.
+    pub corrected: String,
+    /// Inserted spaces plus the edit distances of the corrections.
+    pub distance_sum: usize,
+    /// Sum of the log10 probabilities of the corrected words.
+    pub log_probability: f64,
+}
+
+/// One corrected piece of a compound lookup.
+struct Part {
+    term: String,
+    distance: usize,
+    probability: f64,
+}
+
+impl Part {
+    fn found(s: Suggestion) -> Self {
+        Self {
+            probability: probability(s.frequency),
+            distance: s.distance as usize,
+            term: s.term,
+        }
+    }
+
+    /// `word` kept as is, one edit worse than anything `lookup` could return.
+    fn unknown(word: &str, max_distance: u8) -> Self {
+        Self {
+            term: word.to_string(),
+            distance: max_distance as usize + 1,
+            probability: unknown_probability(word.chars().count()),
+        }
+    }
+}
+
+fn probability(frequency: usize) -> f64 {
+    frequency as f64 / CORPUS_SIZE
+}
+
+fn unknown_probability(len: usize) -> f64 {
+    10.0 / (CORPUS_SIZE * 10f64.powi(len as i32))
+}
+
+fn top<L: Lookup + ?Sized>(dict: &L, term: &str, max_distance: u8) -> Option<Suggestion> {
+    dict.lookup(term, max_distance, Verbosity::Top)
+        .into_iter()
+        .next()
+}
+
+/// Shared implementation of `Lookup::lookup_compound`, ranking each word's
+/// candidates in context if `bigrams` are given.
+pub(crate) fn lookup_compound<L: Lookup + ?Sized>(
+    dict: &L,
+    input: &str,
+    max_distance: u8,
+    bigrams: Option<&BigramDictionary>,
+) -> Suggestion {
+    let words: Vec<String> = input.split_whitespace().map(str::to_lowercase).collect();
+    let mut parts: Vec<Part> = Vec::with_capacity(words.len());
+    let mut last_merged = false;
+    for (i, word) in words.iter().enumerate() {
+        let best = match bigrams {
+            Some(bigrams) => {
+                let previous = parts.last().and_then(|p| p.term.rsplit(' ').next());
+                let next = words.get(i + 1).map(String::as_str);
+                bigram::lookup_context(
+                    dict,
+                    bigrams,
+                    word,
+                    previous,
+                    next,
+                    max_distance,
+                    Verbosity::Top,
+                )
+                .into_iter()
+                .next()
+            }
+            None => top(dict, word, max_distance),
+        };
+
+        // Merge with the previous word, unless that is already a merge.
+        if i > 0 && !last_merged {
+            let joined = format!("{}{}", words[i - 1], word);
+            if let Some(merged) = top(dict, &joined, max_distance) {
+                let previous = &parts[parts.len() - 1];
+                let current = best
+                    .clone()
+                    .map_or_else(|| Part::unknown(word, max_distance), Part::found);
+                let separate = previous.distance + current.distance;
+                // Removing the space costs one edit.
+                let merged = Part {
+                    distance: merged.distance as usize + 1,
+                    ..Part::found(merged)
+                };
+                if merged.distance < separate
+                    || (merged.distance == separate
+                        && merged.probability > previous.probability * current.probability)
+                {
+                    *parts.last_mut().unwrap() = merged;
+                    last_merged = true;
+                    continue;
+                }
+            }
+        }
+        last_merged = false;
+
+        // Exact words and single characters are never split.
+        let part = match best {
+            Some(s) if s.distance == 0 || word.chars().count() == 1 => Part::found(s),
+            best => best_split(dict, word, best.map(Part::found), max_distance)
+                .unwrap_or_else(|| Part::unknown(word, max_distance)),
+        };
+        parts.push(part);
+    }
+
+    let term = parts
+        .iter()
+        .map(|p| p.term.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let probability: f64 = parts.iter().map(|p| p.probability).product();
+    Suggestion {
+        distance: OptimalStringAlignment.distance(&words.join(" "), &term),
+        frequency: (CORPUS_SIZE * probability) as usize,
+        term,
+        origin: Origin::EditDistance,
+        dictionary: None,
+        score: None,
+    }
+}
+
+/// The closest (then most likely) of `best` and every split of `word` into
+/// two corrected words.
+fn best_split<L: Lookup + ?Sized>(
+    dict: &L,
+    word: &str,
+    mut best: Option<Part>,
+    max_distance: u8,
+) -> Option<Part> {
+    let chars: Vec<char> = word.chars().collect();
+    for j in 1..chars.len() {
+        let left: String = chars[..j].iter().collect();
+        let Some(left) = top(dict, &left, max_distance) else {
+            continue;
+        };
+        let right: String = chars[j..].iter().collect();
+        let Some(right) = top(dict, &right, max_distance) else {
+            continue;
+        };
+        let term = format!("{} {}", left.term, right.term);
+        let distance = OptimalStringAlignment.distance(word, &term) as usize;
+        if let Some(b) = &best {
+            if distance > b.distance {
+                continue;
+            }
+            if distance < b.distance {
+                best = None;
+            }
+        }
+        let probability = probability(left.frequency) * probability(right.frequency);
+        if best.as_ref().is_none_or(|b| probability > b.probability) {
+            best = Some(Part {
+                term,
+                distance,
+                probability,
+            });
+        }
+    }
+    best
+}
+
+/// Longest segment tried by `word_segmentation` when the dictionary does not
+/// know its word lengths.
+const MAX_SEGMENT_LEN: usize = 24;
+
+/// Shared implementation of `Lookup::word_segmentation`.
+///
+/// `compositions` is a ring of the best segmentations of the input prefixes
+/// ending at the last `size` positions; segments are at most as long as the
+/// longest dictionary word, or `MAX_SEGMENT_LEN` if that is unknown.
+pub(crate) fn word_segmentation<L: Lookup + ?Sized>(
+    dict: &L,
+    input: &str,
+    max_distance: u8,
+) -> Segmentation {
+    let chars: Vec<char> = input.chars().collect();
+    if chars.is_empty() {
+        return Segmentation::default();
+    }
+    let max_len = dict
+        .word_lengths()
+        .map_or(MAX_SEGMENT_LEN, |(_, max)| max.max(1));
+    let size = max_len.min(chars.len());
+    let mut compositions = vec![Segmentation::default(); size];
+    // Index of the composition ending just before position `j`.
+    let mut circular = size - 1;
+
+    for j in 0..chars.len() {
+        for i in 1..=(chars.len() - j).min(max_len) {
+            let mut part: String = chars[j..j + i].iter().collect();
+            // A segment starting at an existing space needs no inserted one.
+            let separator = match part.chars().next() {
+                Some(c) if c.is_whitespace() => {
+                    part.remove(0);
+                    0
+                }
+                _ => 1,
+            };
+            // Other spaces are dropped, each counting as an edit.
+            let len_with_spaces = part.chars().count();
+            part.retain(|c| !c.is_whitespace());
+            let len = part.chars().count();
+            let mut distance = len_with_spaces - len;
+
+            let (word, log_probability) = match top(dict, &part.to_lowercase(), max_distance) {
+                Some(s) => {
+                    distance += s.distance as usize;
+                    (s.term, probability(s.frequency).log10())
+                }
+                None => {
+                    distance += len;
+                    (part.clone(), unknown_probability(len).log10())
+                }
+            };
+
+            let destination = (i + circular) % size;
+            if j == 0 {
+                compositions[destination] = Segmentation {
+                    segmented: part,
+                    corrected: word,
+                    distance_sum: distance,
+                    log_probability,
+                };
+                continue;
+            }
+            let previous = &compositions[circular];
+            let current = &compositions[destination];
+            let distance_sum = previous.distance_sum + separator + distance;
+            let log_probability = previous.log_probability + log_probability;
+            // Replace stale entries (a full ring), closer segmentations, and
+            // equally close but more likely ones.
+            if i == max_len
+                || distance_sum < current.distance_sum
+                || ((previous.distance_sum + distance == current.distance_sum
+                    || distance_sum == current.distance_sum)
+                    && current.log_probability < log_probability)
+            {
+                compositions[destination] = Segmentation {
+                    segmented: format!("{} {}", previous.segmented, part),
+                    corrected: format!("{} {}", previous.corrected, word),
+                    distance_sum,
+                    log_probability,
+                };
+            }
+        }
+        circular = (circular + 1) % size;
+    }
+    compositions.swap_remove(circular)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::symspell::SymSpell;
+
+    fn dictionary() -> SymSpell {
+        SymSpell::from_iter(
+            2,
+            [
+                ("where", 1000usize),
+                ("is", 5000),
+                ("the", 9000),
+                ("love", 800),
+                ("he", 3000),
+                ("hello", 400),
+                ("world", 600),
+                ("quick", 300),
+                ("brown", 250),
+                ("fox", 200),
+                ("in", 6000),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_lookup_compound() {
+        let sym = dictionary();
+
+        // Split, correct and keep.
+        let result = sym.lookup_compound("whereis th elove", 2);
+        assert_eq!(result.term, "where is the love");
+        assert_eq!(result.distance, 2);
+
+        // Merge.
+        assert_eq!(sym.lookup_compound("hel lo wrld", 2).term, "hello world");
+        // Split run-together words.
+        assert_eq!(sym.lookup_compound("inthe", 2).term, "in the");
+        // Correct input is left alone.
+        let exact = sym.lookup_compound("Hello World", 2);
+        assert_eq!((exact.term.as_str(), exact.distance), ("hello world", 0));
+        // Unknown words are kept.
+        assert_eq!(sym.lookup_compound("xqzvj fox", 2).term, "xqzvj fox");
+        assert_eq!(sym.lookup_compound("", 2).term, "");
+    }
+
+    #[test]
+    fn test_word_segmentation() {
+        let sym = dictionary();
+
+        let result = sym.word_segmentation("thequickbrownfox", 0);
+        assert_eq!(result.segmented, "the quick brown fox");
+        assert_eq!(result.corrected, "the quick brown fox");
+        assert_eq!(result.distance_sum, 3);
+
+        let result = sym.word_segmentation("thequikbrown fox", 1);
+        assert_eq!(result.segmented, "the quik brown fox");
+        assert_eq!(result.corrected, "the quick brown fox");
+        // Two inserted spaces and one correction; the existing space is free.
+        assert_eq!(result.distance_sum, 3);
+        assert!(result.log_probability < 0.0);
+
+        assert_eq!(sym.word_segmentation("", 2).corrected, "");
+    }
+}
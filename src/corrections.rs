@@ -0,0 +1,183 @@
+/*!
+corrections module
+
+A `CorrectionTable` maps known misspellings to their fixes (`"teh"` ->
+`"the"`, `"recieve"` -> `"receive"`). Attached to `SymSpell` or
+`EmbeddedSymSpell` with `with_corrections` (or the `corrections` option of
+`include_dictionary!`), it is consulted before the deletion index: a hit is
+returned as the top suggestion, with `Origin::CorrectionTable`, whatever the
+frequencies of other candidates.
+
+- `Verbosity::Top` and `Closest` return only the table's correction;
+  `All` returns it followed by the usual suggestions.
+- The suggestion's `distance` is the metric distance to the correction and
+  may exceed `max_distance`; its `frequency` is the correction's dictionary
+  frequency, 0 if it is not a dictionary word (e.g. `"alot"` -> `"a lot"`).
+- Blocked corrections (see the `blocklist` module) are ignored.
+
+Table files have one entry per line: the misspelling, whitespace, then the
+correction (the rest of the line, which may contain spaces). Blank lines and
+lines starting with `#` are skipped.
+*/
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::loader::LoadOptions;
+
+/// Known misspellings and their corrections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorrectionTable {
+    corrections: HashMap<String, String>,
+}
+
+impl CorrectionTable {
+    /// An empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a table from `reader`. Only `options.lowercase` applies, to both
+    /// sides of each entry; a repeated misspelling keeps its last correction.
+    ///
+    /// Lines without a correction are reported as `io::ErrorKind::InvalidData`
+    /// with their line number.
+    pub fn read<R: BufRead>(reader: R, options: &LoadOptions) -> io::Result<Self> {
+        let mut table = Self::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let Some((misspelling, correction)) = trimmed.split_once(char::is_whitespace) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected correction: {}", lineno + 1, trimmed),
+                ));
+            };
+            let correction = correction.trim();
+            if options.lowercase {
+                table.insert(misspelling.to_lowercase(), correction.to_lowercase());
+            } else {
+                table.insert(misspelling, correction);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Read a table file (see `read`).
+    pub fn from_file<P: AsRef<Path>>(path: P, options: &LoadOptions) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?), options)
+    }
+
+    /// Correct `misspelling` to `correction`, returning the previous
+    /// correction if there was one.
+    pub fn insert<S: Into<String>, T: Into<String>>(
+        &mut self,
+        misspelling: S,
+        correction: T,
+    ) -> Option<String> {
+        self.corrections
+            .insert(misspelling.into(), correction.into())
+    }
+
+    /// Remove the entry for `misspelling`, returning its correction.
+    pub fn remove(&mut self, misspelling: &str) -> Option<String> {
+        self.corrections.remove(misspelling)
+    }
+
+    /// The correction of `misspelling`, if it is in the table.
+    pub fn get(&self, misspelling: &str) -> Option<&str> {
+        self.corrections.get(misspelling).map(String::as_str)
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.corrections.len()
+    }
+
+    /// Returns true if the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.corrections.is_empty()
+    }
+
+    /// Iterate over `(misspelling, correction)` entries, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.corrections
+            .iter()
+            .map(|(m, c)| (m.as_str(), c.as_str()))
+    }
+}
+
+impl<S: Into<String>, T: Into<String>> FromIterator<(S, T)> for CorrectionTable {
+    fn from_iter<I: IntoIterator<Item = (S, T)>>(iter: I) -> Self {
+        let mut table = Self::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<S: Into<String>, T: Into<String>> Extend<(S, T)> for CorrectionTable {
+    fn extend<I: IntoIterator<Item = (S, T)>>(&mut self, iter: I) {
+        for (misspelling, correction) in iter {
+            self.insert(misspelling, correction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocklist::Blocklist;
+    use crate::symspell::{Origin, SymSpell, Verbosity};
+
+    #[test]
+    fn test_correction_table() {
+        let text = "# known typos\nTeh the\nalot   a lot\nthr three\n";
+        let table =
+            CorrectionTable::read(text.as_bytes(), &LoadOptions::new().with_lowercase(true))
+                .unwrap();
+        assert_eq!(table.get("teh"), Some("the"));
+        assert_eq!(table.get("alot"), Some("a lot"));
+        let err = CorrectionTable::read("teh\n".as_bytes(), &LoadOptions::new()).unwrap_err();
+        assert!(err.to_string().starts_with("line 1:"));
+
+        let sym = SymSpell::from_iter(
+            2,
+            [
+                ("the", 100usize),
+                ("ten", 5000),
+                ("three", 10),
+                ("thru", 50),
+            ],
+        )
+        .with_corrections(table);
+
+        // "ten" is more frequent at the same distance, but the table wins.
+        let top = sym.lookup("teh", 2, Verbosity::Top);
+        assert_eq!(top.len(), 1);
+        assert_eq!(
+            (top[0].term.as_str(), top[0].frequency, top[0].distance),
+            ("the", 100, 1)
+        );
+        assert_eq!(top[0].origin, Origin::CorrectionTable);
+        assert_eq!(sym.lookup("teh", 2, Verbosity::Closest), top);
+
+        let all = sym.lookup("teh", 2, Verbosity::All);
+        assert_eq!(all[0], top[0]);
+        assert_eq!(all.iter().filter(|s| s.term == "the").count(), 1);
+        assert!(all.iter().skip(1).all(|s| s.origin == Origin::EditDistance));
+
+        // Corrections to phrases, and beyond `max_distance`.
+        let alot = &sym.lookup("alot", 2, Verbosity::Top)[0];
+        assert_eq!((alot.term.as_str(), alot.frequency), ("a lot", 0));
+        assert_eq!(sym.lookup("thr", 1, Verbosity::Top)[0].term, "three");
+
+        // A blocked correction is ignored.
+        let sym = sym.with_blocklist(["three"].into_iter().collect::<Blocklist>());
+        assert_eq!(sym.lookup("thr", 1, Verbosity::Top)[0].term, "the");
+    }
+}
@@ -0,0 +1,739 @@
+/*!
+distance module
+
+Edit-distance metrics used to verify SymSpell candidates.
+
+The deletion index only narrows the search down to plausible candidates; each
+candidate is then verified with a `DistanceMetric`. Three metrics are provided:
+
+- `Levenshtein`: insertions, deletions and substitutions.
+- `OptimalStringAlignment`: Levenshtein plus transposition of adjacent
+  characters, where no substring may be edited more than once. This is what
+  SymSpell has always used and is the default.
+- `DamerauLevenshtein`: unrestricted Damerau-Levenshtein, where transposed
+  characters may be edited further (e.g. `"ca"` -> `"abc"` is 2, not 3).
+
+All metrics work on Unicode scalar values (`char`s) and cap their result at 255.
+`Levenshtein` and `OptimalStringAlignment` use an allocation-free bit-parallel
+algorithm for strings up to 64 characters and reject candidates early in
+`DistanceMetric::distance_within`.
+
+`EditCosts` is a separate, weighted model: it assigns a real-valued cost to each
+edit (optionally per character, or cheaper for adjacent keyboard keys) and is
+used to rank candidates that share the same integer distance. It can also hold
+multi-character confusion pairs (e.g. OCR's `"rn"` <-> `"m"`), each of which
+counts as a single edit.
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::keyboard::KeyboardLayout;
+
+/// A string distance used to verify and rank SymSpell candidates.
+///
+/// Implementations must be symmetric and return `0` only for equal strings.
+/// Results larger than 255 are capped at 255. Lookups prune candidates by
+/// length, so the distance must be at least the difference in character count.
+pub trait DistanceMetric: Send + Sync {
+    /// Distance between `a` and `b`.
+    fn distance(&self, a: &str, b: &str) -> u8;
+
+    /// Distance between `a` and `b` if it is at most `max`, otherwise `None`.
+    ///
+    /// Lookups only need to know whether a candidate is within `max_distance`,
+    /// so implementations should stop as soon as the bound is exceeded. The
+    /// default computes the full `distance`.
+    fn distance_within(&self, a: &str, b: &str, max: u8) -> Option<u8> {
+        let d = self.distance(a, b);
+        (d <= max).then_some(d)
+    }
+}
+
+/// Classic Levenshtein distance (insert, delete, substitute).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Levenshtein;
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transposition,
+/// restricted to editing each substring once). The default metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimalStringAlignment;
+
+/// Unrestricted Damerau-Levenshtein distance (Levenshtein plus adjacent
+/// transposition, with no restriction on editing transposed substrings).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DamerauLevenshtein;
+
+impl DistanceMetric for Levenshtein {
+    fn distance(&self, a: &str, b: &str) -> u8 {
+        bounded(a, b, u8::MAX, false).unwrap_or(u8::MAX)
+    }
+
+    fn distance_within(&self, a: &str, b: &str, max: u8) -> Option<u8> {
+        bounded(a, b, max, false)
+    }
+}
+
+impl DistanceMetric for OptimalStringAlignment {
+    fn distance(&self, a: &str, b: &str) -> u8 {
+        bounded(a, b, u8::MAX, true).unwrap_or(u8::MAX)
+    }
+
+    fn distance_within(&self, a: &str, b: &str, max: u8) -> Option<u8> {
+        bounded(a, b, max, true)
+    }
+}
+
+/// Bounded Levenshtein (or OSA, with `transpositions`) distance.
+///
+/// Strings whose lengths differ by more than `max` are rejected up front. When
+/// the shorter string has at most 64 characters the distance is computed with
+/// the bit-parallel algorithm of Myers/Hyyrö without allocating; longer inputs
+/// fall back to a row-by-row DP. Both stop as soon as the bound is exceeded.
+fn bounded(a: &str, b: &str, max: u8, transpositions: bool) -> Option<u8> {
+    if a == b {
+        return Some(0);
+    }
+    let (alen, blen) = (a.chars().count(), b.chars().count());
+    if alen.abs_diff(blen) > max as usize {
+        return None;
+    }
+    // Both metrics are symmetric: use the shorter string as the pattern.
+    let (pattern, plen, text, tlen) = if alen <= blen {
+        (a, alen, b, blen)
+    } else {
+        (b, blen, a, alen)
+    };
+    if plen == 0 {
+        return Some(tlen as u8);
+    }
+    if plen <= 64 {
+        bit_parallel(pattern, plen, text, tlen, max, transpositions)
+    } else {
+        banded_dp(pattern, text, max, transpositions)
+    }
+}
+
+/// Per-character bit masks of a pattern of at most 64 characters.
+///
+/// ASCII characters use a direct table; others a small inline list, so
+/// building the masks never allocates.
+struct PatternMasks {
+    ascii: [u64; 128],
+    other: [(char, u64); 64],
+    other_len: usize,
+}
+
+impl PatternMasks {
+    fn new(pattern: &str) -> Self {
+        let mut masks = Self {
+            ascii: [0; 128],
+            other: [('\0', 0); 64],
+            other_len: 0,
+        };
+        for (i, c) in pattern.chars().enumerate() {
+            let bit = 1u64 << i;
+            if c.is_ascii() {
+                masks.ascii[c as usize] |= bit;
+            } else if let Some(entry) = masks.other[..masks.other_len]
+                .iter_mut()
+                .find(|(oc, _)| *oc == c)
+            {
+                entry.1 |= bit;
+            } else {
+                masks.other[masks.other_len] = (c, bit);
+                masks.other_len += 1;
+            }
+        }
+        masks
+    }
+
+    fn get(&self, c: char) -> u64 {
+        if c.is_ascii() {
+            self.ascii[c as usize]
+        } else {
+            self.other[..self.other_len]
+                .iter()
+                .find(|(oc, _)| *oc == c)
+                .map_or(0, |(_, m)| *m)
+        }
+    }
+}
+
+/// Hyyrö's bit-parallel formulation of Myers' algorithm, with the OSA
+/// transposition extension when `transpositions` is set.
+fn bit_parallel(
+    pattern: &str,
+    plen: usize,
+    text: &str,
+    tlen: usize,
+    max: u8,
+    transpositions: bool,
+) -> Option<u8> {
+    let peq = PatternMasks::new(pattern);
+    let last = 1u64 << (plen - 1);
+    let (mut vp, mut vn, mut d0, mut pm_prev) = (!0u64, 0u64, 0u64, 0u64);
+    let mut dist = plen;
+
+    for (j, c) in text.chars().enumerate() {
+        let pm = peq.get(c);
+        let tr = if transpositions {
+            ((!d0 & pm) << 1) & pm_prev
+        } else {
+            0
+        };
+        d0 = ((((pm & vp).wrapping_add(vp)) ^ vp) | pm | vn) | tr;
+        let hp = vn | !(d0 | vp);
+        let hn = d0 & vp;
+        if hp & last != 0 {
+            dist += 1;
+        } else if hn & last != 0 {
+            dist -= 1;
+        }
+        let hp = (hp << 1) | 1;
+        let hn = hn << 1;
+        vp = hn | !(d0 | hp);
+        vn = hp & d0;
+        pm_prev = pm;
+
+        // Each remaining text character can lower the distance by at most one.
+        if dist > max as usize + (tlen - j - 1) {
+            return None;
+        }
+    }
+    (dist <= max as usize).then_some(dist as u8)
+}
+
+/// Row-by-row DP used for patterns longer than 64 characters. Stops once two
+/// consecutive rows (a transposition can skip one) exceed `max`.
+fn banded_dp(a: &str, b: &str, max: u8, transpositions: bool) -> Option<u8> {
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    let max = max as usize;
+
+    let mut prev2: Vec<usize> = vec![0; b_chars.len() + 1];
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b_chars.len() + 1];
+    let mut prev_min = 0;
+    for i in 1..=a_chars.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b_chars.len() {
+            let cost = usize::from(a_chars[i - 1] != b_chars[j - 1]);
+            let mut d = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            if transpositions
+                && i > 1
+                && j > 1
+                && a_chars[i - 1] == b_chars[j - 2]
+                && a_chars[i - 2] == b_chars[j - 1]
+            {
+                d = d.min(prev2[j - 2] + 1);
+            }
+            curr[j] = d;
+            row_min = row_min.min(d);
+        }
+        if row_min > max && prev_min > max {
+            return None;
+        }
+        prev_min = row_min;
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let d = prev[b_chars.len()];
+    (d <= max).then_some(d as u8)
+}
+
+impl DistanceMetric for DamerauLevenshtein {
+    fn distance(&self, a: &str, b: &str) -> u8 {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let (alen, blen) = (a_chars.len(), b_chars.len());
+        if alen == 0 || blen == 0 {
+            return cap(alen.max(blen));
+        }
+
+        // Lowrance-Wagner algorithm. The table is offset by one row/column
+        // holding `max_dist` so transpositions reaching before the start are
+        // never chosen.
+        let max_dist = alen + blen;
+        let mut dp: Vec<Vec<usize>> = vec![vec![0; blen + 2]; alen + 2];
+        dp[0][0] = max_dist;
+        for i in 0..=alen {
+            dp[i + 1][0] = max_dist;
+            dp[i + 1][1] = i;
+        }
+        for j in 0..=blen {
+            dp[0][j + 1] = max_dist;
+            dp[1][j + 1] = j;
+        }
+
+        // Last row in which each character of `a` was seen.
+        let mut last_row: HashMap<char, usize> = HashMap::new();
+        for i in 1..=alen {
+            // Last column in this row where a[i-1] matched.
+            let mut last_match_col = 0;
+            for j in 1..=blen {
+                let k = last_row.get(&b_chars[j - 1]).copied().unwrap_or(0);
+                let l = last_match_col;
+                let cost = if a_chars[i - 1] == b_chars[j - 1] {
+                    last_match_col = j;
+                    0
+                } else {
+                    1
+                };
+                dp[i + 1][j + 1] = (dp[i][j] + cost)
+                    .min(dp[i + 1][j] + 1)
+                    .min(dp[i][j + 1] + 1)
+                    .min(dp[k][l] + (i - k - 1) + 1 + (j - l - 1));
+            }
+            last_row.insert(a_chars[i - 1], i);
+        }
+
+        cap(dp[alen + 1][blen + 1])
+    }
+
+    fn distance_within(&self, a: &str, b: &str, max: u8) -> Option<u8> {
+        // Every metric here is at least the length difference.
+        if a.chars().count().abs_diff(b.chars().count()) > max as usize {
+            return None;
+        }
+        let d = self.distance(a, b);
+        (d <= max).then_some(d)
+    }
+}
+
+/// Weighted edit costs for ranking candidates.
+///
+/// Costs describe transforming `a` (the query as typed) into `b` (a dictionary
+/// word): an insertion inserts a character of `b`, a deletion removes a character
+/// of `a`. Every edit costs `1.0` unless configured otherwise.
+///
+/// ```ignore
+/// use symspellrs::{EditCosts, KeyboardLayout};
+/// // Substituting neighbouring keys ("teat" for "test") costs half an edit.
+/// let costs = EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5);
+/// assert!(costs.distance("teat", "test") < costs.distance("text", "test"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditCosts {
+    insertion: f64,
+    deletion: f64,
+    substitution: f64,
+    transposition: f64,
+    char_insertion: HashMap<char, f64>,
+    char_deletion: HashMap<char, f64>,
+    char_substitution: HashMap<(char, char), f64>,
+    confusions: Vec<Confusion>,
+}
+
+/// A directed multi-character replacement `from` -> `to`.
+#[derive(Debug, Clone, PartialEq)]
+struct Confusion {
+    from: String,
+    to: String,
+    from_chars: Vec<char>,
+    to_chars: Vec<char>,
+    cost: f64,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditCosts {
+    /// Unit costs for every edit.
+    pub fn new() -> Self {
+        Self {
+            insertion: 1.0,
+            deletion: 1.0,
+            substitution: 1.0,
+            transposition: 1.0,
+            char_insertion: HashMap::new(),
+            char_deletion: HashMap::new(),
+            char_substitution: HashMap::new(),
+            confusions: Vec::new(),
+        }
+    }
+
+    /// Unit costs plus a built-in set of common OCR confusions.
+    ///
+    /// Includes shape confusions such as `"rn"` <-> `"m"`, `"cl"` <-> `"d"`,
+    /// `"vv"` <-> `"w"`, `"li"` <-> `"h"` and digit/letter pairs such as
+    /// `"0"` <-> `"O"`, `"1"` <-> `"l"` and `"5"` <-> `"S"`.
+    pub fn ocr() -> Self {
+        let pairs: &[(&str, &str, f64)] = &[
+            ("rn", "m", 0.25),
+            ("cl", "d", 0.25),
+            ("vv", "w", 0.25),
+            ("li", "h", 0.4),
+            ("ri", "n", 0.4),
+            ("in", "m", 0.5),
+            ("0", "O", 0.25),
+            ("0", "o", 0.25),
+            ("1", "l", 0.25),
+            ("1", "I", 0.25),
+            ("l", "I", 0.25),
+            ("5", "S", 0.3),
+            ("5", "s", 0.4),
+            ("8", "B", 0.3),
+            ("6", "b", 0.4),
+            ("c", "e", 0.5),
+        ];
+        pairs.iter().fold(Self::new(), |costs, &(a, b, cost)| {
+            costs.with_confusion(a, b, cost)
+        })
+    }
+
+    /// Default cost of inserting any character.
+    pub fn with_insertion_cost(mut self, cost: f64) -> Self {
+        self.insertion = cost;
+        self
+    }
+
+    /// Default cost of deleting any character.
+    pub fn with_deletion_cost(mut self, cost: f64) -> Self {
+        self.deletion = cost;
+        self
+    }
+
+    /// Default cost of substituting one character for another.
+    pub fn with_substitution_cost(mut self, cost: f64) -> Self {
+        self.substitution = cost;
+        self
+    }
+
+    /// Cost of transposing two adjacent characters.
+    pub fn with_transposition_cost(mut self, cost: f64) -> Self {
+        self.transposition = cost;
+        self
+    }
+
+    /// Cost of inserting `c` specifically.
+    pub fn with_char_insertion_cost(mut self, c: char, cost: f64) -> Self {
+        self.char_insertion.insert(c, cost);
+        self
+    }
+
+    /// Cost of deleting `c` specifically.
+    pub fn with_char_deletion_cost(mut self, c: char, cost: f64) -> Self {
+        self.char_deletion.insert(c, cost);
+        self
+    }
+
+    /// Cost of substituting `a` for `b` (and `b` for `a`).
+    pub fn with_char_substitution_cost(mut self, a: char, b: char, cost: f64) -> Self {
+        self.char_substitution.insert((a, b), cost);
+        self.char_substitution.insert((b, a), cost);
+        self
+    }
+
+    /// Treat replacing `a` by `b` (and `b` by `a`) as a single edit costing `cost`.
+    ///
+    /// Either side may span several characters, e.g. `with_confusion("rn", "m", 0.25)`.
+    /// Lookups also try the query with confusions applied, so words reachable
+    /// only through confusions are still found.
+    pub fn with_confusion(mut self, a: &str, b: &str, cost: f64) -> Self {
+        if a.is_empty() || b.is_empty() || a == b {
+            return self;
+        }
+        for (from, to) in [(a, b), (b, a)] {
+            self.confusions.retain(|c| !(c.from == from && c.to == to));
+            self.confusions.push(Confusion {
+                from: from.to_string(),
+                to: to.to_string(),
+                from_chars: from.chars().collect(),
+                to_chars: to.chars().collect(),
+                cost,
+            });
+        }
+        self
+    }
+
+    /// Returns true if any confusion pairs are configured.
+    pub fn has_confusions(&self) -> bool {
+        !self.confusions.is_empty()
+    }
+
+    /// Make substitutions between adjacent keys of `layout` cost `adjacent_cost`.
+    ///
+    /// Both lower- and upper-case forms of each key pair are registered. Pairs
+    /// that already have an explicit substitution cost keep it.
+    pub fn with_keyboard(mut self, layout: KeyboardLayout, adjacent_cost: f64) -> Self {
+        for row in layout.rows() {
+            for a in row.chars() {
+                for b in layout.neighbours(a) {
+                    for (x, y) in [(a, b), (upper(a), upper(b))] {
+                        self.char_substitution
+                            .entry((x, y))
+                            .or_insert(adjacent_cost);
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    fn insertion_cost(&self, c: char) -> f64 {
+        self.char_insertion
+            .get(&c)
+            .copied()
+            .unwrap_or(self.insertion)
+    }
+
+    fn deletion_cost(&self, c: char) -> f64 {
+        self.char_deletion.get(&c).copied().unwrap_or(self.deletion)
+    }
+
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        self.char_substitution
+            .get(&(a, b))
+            .copied()
+            .unwrap_or(self.substitution)
+    }
+
+    /// Weighted optimal string alignment cost of turning `a` into `b`.
+    pub fn distance(&self, a: &str, b: &str) -> f64 {
+        self.align(a, b, false)
+    }
+
+    /// Number of edits needed to turn `a` into `b` when every insertion,
+    /// deletion, substitution, transposition or confusion counts as one.
+    ///
+    /// Without confusions this equals the optimal string alignment distance.
+    pub fn edit_count(&self, a: &str, b: &str) -> u8 {
+        // Costs are whole numbers here, so rounding only removes float noise.
+        self.align(a, b, true).round().min(255.0) as u8
+    }
+
+    /// Every string obtained from `term` by applying up to `max_rewrites`
+    /// confusion replacements, excluding `term` itself. At most `limit`
+    /// variants are returned.
+    pub fn confusion_variants(&self, term: &str, max_rewrites: u8, limit: usize) -> Vec<String> {
+        let mut seen: HashSet<String> = HashSet::new();
+        seen.insert(term.to_string());
+        let mut variants: Vec<String> = Vec::new();
+        let mut frontier: Vec<String> = vec![term.to_string()];
+
+        for _ in 0..max_rewrites {
+            let mut next: Vec<String> = Vec::new();
+            for s in &frontier {
+                for c in &self.confusions {
+                    for (pos, _) in s.match_indices(c.from.as_str()) {
+                        let mut v = String::with_capacity(s.len() + c.to.len());
+                        v.push_str(&s[..pos]);
+                        v.push_str(&c.to);
+                        v.push_str(&s[pos + c.from.len()..]);
+                        if seen.insert(v.clone()) {
+                            if variants.len() >= limit {
+                                return variants;
+                            }
+                            variants.push(v.clone());
+                            next.push(v);
+                        }
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        variants
+    }
+
+    /// Weighted OSA with multi-character confusions. With `unit` set every edit
+    /// costs 1 so the result is an edit count.
+    fn align(&self, a: &str, b: &str, unit: bool) -> f64 {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let (alen, blen) = (a_chars.len(), b_chars.len());
+        let weight = |cost: f64| if unit { 1.0 } else { cost };
+
+        let mut dp: Vec<Vec<f64>> = vec![vec![0.0; blen + 1]; alen + 1];
+        for i in 1..=alen {
+            dp[i][0] = dp[i - 1][0] + weight(self.deletion_cost(a_chars[i - 1]));
+        }
+        for j in 1..=blen {
+            dp[0][j] = dp[0][j - 1] + weight(self.insertion_cost(b_chars[j - 1]));
+        }
+
+        for i in 1..=alen {
+            for j in 1..=blen {
+                let (ac, bc) = (a_chars[i - 1], b_chars[j - 1]);
+                let substitution = if ac == bc {
+                    0.0
+                } else {
+                    weight(self.substitution_cost(ac, bc))
+                };
+                let mut best = (dp[i - 1][j] + weight(self.deletion_cost(ac)))
+                    .min(dp[i][j - 1] + weight(self.insertion_cost(bc)))
+                    .min(dp[i - 1][j - 1] + substitution);
+                if i > 1 && j > 1 && ac == b_chars[j - 2] && a_chars[i - 2] == bc && ac != bc {
+                    best = best.min(dp[i - 2][j - 2] + weight(self.transposition));
+                }
+                for c in &self.confusions {
+                    let (fl, tl) = (c.from_chars.len(), c.to_chars.len());
+                    if fl <= i
+                        && tl <= j
+                        && a_chars[i - fl..i] == c.from_chars[..]
+                        && b_chars[j - tl..j] == c.to_chars[..]
+                    {
+                        best = best.min(dp[i - fl][j - tl] + weight(c.cost));
+                    }
+                }
+                dp[i][j] = best;
+            }
+        }
+
+        dp[alen][blen]
+    }
+}
+
+fn upper(c: char) -> char {
+    let mut up = c.to_uppercase();
+    match (up.next(), up.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+fn cap(d: usize) -> u8 {
+    d.min(255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(Levenshtein.distance("kitten", "sitting"), 3);
+        assert_eq!(Levenshtein.distance("ab", "ba"), 2);
+        assert_eq!(Levenshtein.distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_osa_vs_damerau() {
+        assert_eq!(OptimalStringAlignment.distance("ab", "ba"), 1);
+        assert_eq!(DamerauLevenshtein.distance("ab", "ba"), 1);
+        // OSA may not edit a transposed pair again; unrestricted Damerau may.
+        assert_eq!(OptimalStringAlignment.distance("ca", "abc"), 3);
+        assert_eq!(DamerauLevenshtein.distance("ca", "abc"), 2);
+    }
+
+    #[test]
+    fn test_edit_costs_keyboard() {
+        let unit = EditCosts::new();
+        assert_eq!(unit.distance("teat", "test"), 1.0);
+        assert_eq!(unit.distance("ab", "ba"), 1.0);
+        assert_eq!(unit.distance("", "ab"), 2.0);
+
+        let kb = EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5);
+        // 'a' neighbours 's' on QWERTY, 'p' does not.
+        assert_eq!(kb.distance("teat", "test"), 0.5);
+        assert_eq!(kb.distance("tept", "test"), 1.0);
+        assert_eq!(kb.distance("TEAT", "TEST"), 0.5);
+    }
+
+    #[test]
+    fn test_edit_costs_per_char() {
+        let costs = EditCosts::new()
+            .with_char_deletion_cost('h', 0.25)
+            .with_insertion_cost(2.0);
+        assert_eq!(costs.distance("hello", "ello"), 0.25);
+        assert_eq!(costs.distance("ello", "hello"), 2.0);
+    }
+
+    #[test]
+    fn test_edit_costs_confusions() {
+        let ocr = EditCosts::ocr();
+        assert_eq!(ocr.edit_count("rnodern", "modem"), 2);
+        assert_eq!(ocr.distance("rnodern", "modem"), 0.5);
+        assert_eq!(ocr.edit_count("clog", "dog"), 1);
+        assert_eq!(ocr.edit_count("F0O", "FOO"), 1);
+        // Without confusions the edit count is plain OSA.
+        assert_eq!(EditCosts::new().edit_count("rnodern", "modem"), 4);
+
+        let mut variants = ocr.confusion_variants("rnodern", 2, 100);
+        variants.sort();
+        assert!(variants.contains(&"modem".to_string()));
+        assert!(variants.contains(&"modern".to_string()));
+    }
+
+    /// Reference full-table Levenshtein/OSA used to check the bit-parallel code.
+    fn reference(a: &str, b: &str, transpositions: bool) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for (i, row) in dp.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in dp[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                dp[i][j] = (dp[i - 1][j] + 1)
+                    .min(dp[i][j - 1] + 1)
+                    .min(dp[i - 1][j - 1] + cost);
+                if transpositions && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+                {
+                    dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        dp[a.len()][b.len()]
+    }
+
+    #[test]
+    fn test_bounded_matches_reference() {
+        // Small deterministic LCG over a tiny alphabet so edits collide often.
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = |n: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+        let alphabet = ['a', 'b', 'c', 'é', 'ß'];
+        let mut word = |max_len: u64| -> String {
+            let len = next(max_len);
+            (0..len)
+                .map(|_| alphabet[next(alphabet.len() as u64) as usize])
+                .collect()
+        };
+        for round in 0..2000 {
+            // Mostly short words plus some beyond the 64-char bit-parallel limit.
+            let len = if round % 10 == 0 { 90 } else { 12 };
+            let (a, b) = (word(len), word(len));
+            for (metric, tr) in [
+                (&Levenshtein as &dyn DistanceMetric, false),
+                (&OptimalStringAlignment, true),
+            ] {
+                let expected = reference(&a, &b, tr);
+                assert_eq!(
+                    metric.distance(&a, &b) as usize,
+                    expected.min(255),
+                    "{a} {b}"
+                );
+                for max in [0u8, 1, 2, 3] {
+                    let within = metric.distance_within(&a, &b, max);
+                    let want = (expected <= max as usize).then_some(expected as u8);
+                    assert_eq!(within, want, "{a} {b} max={max}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_chars() {
+        assert_eq!(OptimalStringAlignment.distance("café", "cafe"), 1);
+        assert_eq!(Levenshtein.distance("naïve", "naive"), 1);
+        assert_eq!(DamerauLevenshtein.distance("éa", "aé"), 1);
+    }
+}
@@ -0,0 +1,392 @@
+/*!
+identifier module
+
+Spell-checking of source-code identifiers. `split_identifier` breaks
+`camelCase`, `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE` and
+`kebab-case` identifiers into subwords (`parseHttpRespnse` -> `parse`, `Http`,
+`Respnse`), `Lookup::check_identifier` checks each subword and reassembles
+suggestions in the identifier's own convention (`parseHttpResponse`).
+
+Subwords are looked up in lower case and corrected in their original casing.
+Digits, single letters and (outside all-caps identifiers) acronyms such as
+`HTTP` are not checked.
+
+`IdentifierTokenizer` applies the same splitting to `check_text`, for
+checking whole source files.
+*/
+
+use std::ops::Range;
+
+use crate::lookup::Lookup;
+use crate::symspell::{Suggestion, Verbosity};
+use crate::text::match_case;
+use crate::tokenize::{IgnoreRule, Tokenizer};
+
+/// Maximum number of reassembled identifiers in `IdentifierCheck::suggestions`.
+const MAX_IDENTIFIER_SUGGESTIONS: usize = 5;
+
+/// Naming convention of an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `camelCase`
+    Camel,
+    /// `PascalCase`
+    Pascal,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// Anything else, e.g. `Mixed_Case`.
+    Mixed,
+}
+
+impl CaseStyle {
+    /// The convention `identifier` is written in.
+    pub fn detect(identifier: &str) -> CaseStyle {
+        let has_upper = identifier.chars().any(char::is_uppercase);
+        let has_lower = identifier.chars().any(char::is_lowercase);
+        if identifier.contains('_') {
+            match (has_upper, has_lower) {
+                (_, false) => CaseStyle::ScreamingSnake,
+                (false, _) => CaseStyle::Snake,
+                _ => CaseStyle::Mixed,
+            }
+        } else if identifier.contains('-') {
+            if has_upper {
+                CaseStyle::Mixed
+            } else {
+                CaseStyle::Kebab
+            }
+        } else if !has_upper {
+            CaseStyle::Lower
+        } else if !has_lower {
+            CaseStyle::Upper
+        } else if identifier.starts_with(char::is_lowercase) {
+            CaseStyle::Camel
+        } else if identifier.starts_with(char::is_uppercase) {
+            CaseStyle::Pascal
+        } else {
+            CaseStyle::Mixed
+        }
+    }
+
+    /// Join `words` in this convention (`Mixed` concatenates them as given).
+    pub fn join<S: AsRef<str>>(&self, words: &[S]) -> String {
+        let words = words.iter().map(AsRef::as_ref);
+        match self {
+            CaseStyle::Lower => words.map(str::to_lowercase).collect(),
+            CaseStyle::Upper => words.map(str::to_uppercase).collect(),
+            CaseStyle::Camel => words
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalize(w)
+                    }
+                })
+                .collect(),
+            CaseStyle::Pascal => words.map(capitalize).collect(),
+            CaseStyle::Snake => words.map(str::to_lowercase).collect::<Vec<_>>().join("_"),
+            CaseStyle::ScreamingSnake => words.map(str::to_uppercase).collect::<Vec<_>>().join("_"),
+            CaseStyle::Kebab => words.map(str::to_lowercase).collect::<Vec<_>>().join("-"),
+            CaseStyle::Mixed => words.collect(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Byte ranges of the subwords of `identifier`.
+///
+/// `_` and `-` separate subwords and are not part of any; a new subword also
+/// starts at a lower-to-upper case change (`parse|Http`), before the last
+/// capital of an upper-case run followed by lower case (`HTTP|Response`) and
+/// between letters and digits (`utf|8|Decoder`).
+pub fn split_identifier(identifier: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = identifier.char_indices().collect();
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    for (k, &(i, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(s) = start.take() {
+                parts.push(s..i);
+            }
+            continue;
+        }
+        if let (Some(s), Some(&(_, prev))) = (start, k.checked_sub(1).map(|p| &chars[p])) {
+            let next = chars.get(k + 1).map(|&(_, n)| n);
+            let boundary = (prev.is_lowercase() && c.is_uppercase())
+                || (prev.is_uppercase()
+                    && c.is_uppercase()
+                    && next.is_some_and(char::is_lowercase))
+                || (prev.is_ascii_digit() != c.is_ascii_digit());
+            if boundary {
+                parts.push(s..i);
+                start = Some(i);
+            }
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        parts.push(s..identifier.len());
+    }
+    parts
+}
+
+/// A misspelled subword of an identifier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubwordMisspelling {
+    /// The subword as written.
+    pub subword: String,
+    /// Byte offsets of the subword in the identifier.
+    pub byte_range: Range<usize>,
+    /// Ranked replacements in the casing of `subword`.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Result of `Lookup::check_identifier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierCheck {
+    /// The checked identifier.
+    pub identifier: String,
+    /// Its naming convention.
+    pub style: CaseStyle,
+    /// Misspelled subwords, in order.
+    pub misspellings: Vec<SubwordMisspelling>,
+    /// Corrected identifiers in the original convention, best first.
+    pub suggestions: Vec<String>,
+}
+
+impl IdentifierCheck {
+    /// Returns true if no subword is misspelled.
+    pub fn is_correct(&self) -> bool {
+        self.misspellings.is_empty()
+    }
+}
+
+/// Returns true if `subword` of an identifier in `style` should be checked.
+fn is_checked(subword: &str, style: CaseStyle) -> bool {
+    let all_caps = matches!(style, CaseStyle::Upper | CaseStyle::ScreamingSnake);
+    subword.chars().nth(1).is_some()
+        && !subword.starts_with(|c: char| c.is_ascii_digit())
+        && (all_caps || !IgnoreRule::Acronym.matches(subword))
+}
+
+/// Shared implementation of `Lookup::check_identifier`.
+pub(crate) fn check_identifier<L: Lookup + ?Sized>(
+    dictionary: &L,
+    identifier: &str,
+    max_distance: u8,
+) -> IdentifierCheck {
+    let style = CaseStyle::detect(identifier);
+    let misspellings: Vec<SubwordMisspelling> = split_identifier(identifier)
+        .into_iter()
+        .filter_map(|range| {
+            let subword = &identifier[range.clone()];
+            let lower = subword.to_lowercase();
+            if !is_checked(subword, style) || dictionary.contains(&lower) {
+                return None;
+            }
+            let mut suggestions = dictionary.lookup(&lower, max_distance, Verbosity::Closest);
+            for s in &mut suggestions {
+                s.term = match_case(subword, &s.term);
+            }
+            Some(SubwordMisspelling {
+                subword: subword.to_string(),
+                byte_range: range,
+                suggestions,
+            })
+        })
+        .collect();
+
+    // The best replacement for every subword first, then variants using the
+    // next-best replacement for one subword at a time.
+    let mut choices = vec![vec![0; misspellings.len()]];
+    for (i, m) in misspellings.iter().enumerate() {
+        for k in 1..m.suggestions.len() {
+            let mut choice = vec![0; misspellings.len()];
+            choice[i] = k;
+            choices.push(choice);
+        }
+    }
+    let mut suggestions: Vec<String> = Vec::new();
+    if misspellings.iter().any(|m| !m.suggestions.is_empty()) {
+        for choice in choices {
+            let candidate = reassemble(identifier, &misspellings, &choice);
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+            if suggestions.len() == MAX_IDENTIFIER_SUGGESTIONS {
+                break;
+            }
+        }
+    }
+
+    IdentifierCheck {
+        identifier: identifier.to_string(),
+        style,
+        misspellings,
+        suggestions,
+    }
+}
+
+/// `identifier` with each misspelled subword replaced by its `choice`-th
+/// suggestion (or kept, if it has none), separators and other subwords intact.
+fn reassemble(identifier: &str, misspellings: &[SubwordMisspelling], choice: &[usize]) -> String {
+    let mut out = String::with_capacity(identifier.len());
+    let mut pos = 0;
+    for (m, &k) in misspellings.iter().zip(choice) {
+        out.push_str(&identifier[pos..m.byte_range.start]);
+        match m.suggestions.get(k) {
+            Some(s) => out.push_str(&s.term),
+            None => out.push_str(&m.subword),
+        }
+        pos = m.byte_range.end;
+    }
+    out.push_str(&identifier[pos..]);
+    out
+}
+
+/// A `Tokenizer` for source code: splits text into identifiers (runs of
+/// letters, digits and `_`) and those into subwords, skipping subwords that
+/// `Lookup::check_identifier` would not check.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentifierTokenizer;
+
+impl Tokenizer for IdentifierTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>> {
+        let mut tokens = Vec::new();
+        let mut push_identifier = |range: Range<usize>| {
+            let identifier = &text[range.clone()];
+            let style = CaseStyle::detect(identifier);
+            for part in split_identifier(identifier) {
+                if is_checked(&identifier[part.clone()], style) {
+                    tokens.push(range.start + part.start..range.start + part.end);
+                }
+            }
+        };
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            if c.is_alphanumeric() || c == '_' {
+                start.get_or_insert(i);
+            } else if let Some(s) = start.take() {
+                push_identifier(s..i);
+            }
+        }
+        if let Some(s) = start {
+            push_identifier(s..text.len());
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::SymSpell;
+
+    fn parts(identifier: &str) -> Vec<&str> {
+        split_identifier(identifier)
+            .into_iter()
+            .map(|r| &identifier[r])
+            .collect()
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(parts("parseHttpRespnse"), ["parse", "Http", "Respnse"]);
+        assert_eq!(parts("max_retry_cuont"), ["max", "retry", "cuont"]);
+        assert_eq!(parts("HTTPResponse"), ["HTTP", "Response"]);
+        assert_eq!(parts("utf8Decoder"), ["utf", "8", "Decoder"]);
+        assert_eq!(parts("__init__"), ["init"]);
+        assert_eq!(parts("user-account-id"), ["user", "account", "id"]);
+        assert_eq!(parts("MAX_RETRY"), ["MAX", "RETRY"]);
+    }
+
+    #[test]
+    fn test_case_style() {
+        assert_eq!(CaseStyle::detect("parseHttp"), CaseStyle::Camel);
+        assert_eq!(CaseStyle::detect("HttpParser"), CaseStyle::Pascal);
+        assert_eq!(CaseStyle::detect("max_retry"), CaseStyle::Snake);
+        assert_eq!(CaseStyle::detect("MAX_RETRY"), CaseStyle::ScreamingSnake);
+        assert_eq!(CaseStyle::detect("max-retry"), CaseStyle::Kebab);
+        assert_eq!(CaseStyle::detect("Max_retry"), CaseStyle::Mixed);
+
+        let words = ["parse", "HTTP", "response"];
+        assert_eq!(CaseStyle::Camel.join(&words), "parseHttpResponse");
+        assert_eq!(CaseStyle::Pascal.join(&words), "ParseHttpResponse");
+        assert_eq!(CaseStyle::Snake.join(&words), "parse_http_response");
+        assert_eq!(
+            CaseStyle::ScreamingSnake.join(&words),
+            "PARSE_HTTP_RESPONSE"
+        );
+        assert_eq!(CaseStyle::Kebab.join(&words), "parse-http-response");
+    }
+
+    #[test]
+    fn test_check_identifier() {
+        let words = [
+            ("parse", 50usize),
+            ("http", 40),
+            ("response", 30),
+            ("responses", 5),
+            ("max", 60),
+            ("retry", 20),
+            ("count", 50),
+            ("court", 10),
+            ("server", 30),
+        ];
+        let sym = SymSpell::from_iter(2, words);
+
+        let check = sym.check_identifier("parseHttpRespnse");
+        assert_eq!(check.style, CaseStyle::Camel);
+        assert_eq!(check.misspellings.len(), 1);
+        assert_eq!(check.misspellings[0].subword, "Respnse");
+        assert_eq!(check.misspellings[0].byte_range, 9..16);
+        assert_eq!(check.suggestions[0], "parseHttpResponse");
+
+        let check = sym.check_identifier("max_retry_cuont");
+        assert_eq!(check.suggestions, ["max_retry_count"]);
+        let check = sym.check_identifier("MAX_RETRY_CUONT");
+        assert_eq!(check.suggestions, ["MAX_RETRY_COUNT"]);
+
+        // "HTTP" is an acronym inside a Pascal-case identifier and is not checked.
+        let check = sym.check_identifier("HTTPServr");
+        assert_eq!(check.suggestions, ["HTTPServer"]);
+
+        assert!(sym.check_identifier("retry_count").is_correct());
+    }
+
+    #[test]
+    fn test_identifier_tokenizer() {
+        let sym = SymSpell::from_iter(2, [("parse", 1usize), ("count", 1), ("let", 1)]);
+        let options = crate::text::CheckOptions::new().with_tokenizer(IdentifierTokenizer);
+        let source = "let cuont = parseHTTP(x1, retry_cuont);";
+        let found: Vec<(String, usize)> = sym
+            .check_text_with(source, options)
+            .map(|m| (m.token, m.column))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("cuont".to_string(), 5),
+                ("retry".to_string(), 27),
+                ("cuont".to_string(), 33)
+            ]
+        );
+    }
+}
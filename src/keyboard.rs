@@ -0,0 +1,98 @@
+/*!
+keyboard module
+
+Physical keyboard layouts used to derive cheaper substitution costs for keys
+that sit next to each other (see `EditCosts::with_keyboard`).
+
+Layouts are modelled as staggered rows of lower-case keys, digit row first.
+A key at `(row, col)` is adjacent to its left/right neighbours, to `(row - 1, col)`
+and `(row - 1, col + 1)` above it and to `(row + 1, col - 1)` and `(row + 1, col)`
+below it, which matches the usual half-key stagger of typewriter layouts.
+*/
+
+/// A built-in keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyboardLayout {
+    /// US/UK QWERTY.
+    Qwerty,
+    /// French AZERTY.
+    Azerty,
+    /// German QWERTZ.
+    Qwertz,
+}
+
+impl KeyboardLayout {
+    /// Rows of the layout from top (digits) to bottom, lower-case.
+    pub fn rows(&self) -> &'static [&'static str] {
+        match self {
+            KeyboardLayout::Qwerty => &["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            KeyboardLayout::Azerty => &["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn"],
+            KeyboardLayout::Qwertz => &["1234567890", "qwertzuiopü", "asdfghjklöä", "yxcvbnm"],
+        }
+    }
+
+    /// Row and column of `key` (case-insensitive), if it is on the layout.
+    pub fn position(&self, key: char) -> Option<(usize, usize)> {
+        let key = key.to_lowercase().next().unwrap_or(key);
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(r, row)| row.chars().position(|c| c == key).map(|col| (r, col)))
+    }
+
+    /// Returns true if `a` and `b` are distinct, physically adjacent keys.
+    pub fn are_adjacent(&self, a: char, b: char) -> bool {
+        let (Some((ra, ca)), Some((rb, cb))) = (self.position(a), self.position(b)) else {
+            return false;
+        };
+        if ra == rb {
+            return ca.abs_diff(cb) == 1;
+        }
+        if ra + 1 == rb {
+            // `b` is on the row below `a`
+            return cb == ca || cb + 1 == ca;
+        }
+        if rb + 1 == ra {
+            // `b` is on the row above `a`
+            return ca == cb || ca + 1 == cb;
+        }
+        false
+    }
+
+    /// All keys adjacent to `key`.
+    pub fn neighbours(&self, key: char) -> Vec<char> {
+        self.rows()
+            .iter()
+            .flat_map(|row| row.chars())
+            .filter(|&c| self.are_adjacent(key, c))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qwerty_adjacency() {
+        let kb = KeyboardLayout::Qwerty;
+        assert!(kb.are_adjacent('s', 'a'));
+        assert!(kb.are_adjacent('s', 'w'));
+        assert!(kb.are_adjacent('s', 'e'));
+        assert!(kb.are_adjacent('s', 'z'));
+        assert!(kb.are_adjacent('S', 'x'));
+        assert!(!kb.are_adjacent('s', 'r'));
+        assert!(!kb.are_adjacent('s', 's'));
+        let mut n = kb.neighbours('g');
+        n.sort_unstable();
+        assert_eq!(n, vec!['b', 'f', 'h', 't', 'v', 'y']);
+    }
+
+    #[test]
+    fn test_layouts_differ() {
+        assert!(KeyboardLayout::Qwerty.are_adjacent('t', 'y'));
+        assert!(!KeyboardLayout::Qwertz.are_adjacent('t', 'y'));
+        assert!(KeyboardLayout::Qwertz.are_adjacent('t', 'z'));
+        assert!(KeyboardLayout::Azerty.are_adjacent('a', 'z'));
+    }
+}
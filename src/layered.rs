@@ -0,0 +1,392 @@
+/*!
+layered module
+
+`LayeredDictionary` puts a mutable user layer on top of a read-only base
+dictionary, so users can add their own words without touching the shipped
+one. The base is any `Lookup`: an `EmbeddedSymSpell` compiled into the
+binary, a runtime `SymSpell`, a `SharedSymSpell`, ...
+
+- The user layer is a `SymSpell` built for the base's `max_distance`,
+  distance metric and noisy-channel scoring. Its words are lower-cased, like typical base dictionaries,
+  and all get the same frequency (`with_user_frequency`, 1 by default).
+- The user layer persists to a plain-text word list, one word per line (the
+  `loader` format without frequencies). `open` loads it and `save` rewrites it.
+- Lookups query both layers and merge the results. Correction-table hits
+  come first and each layer keeps its own order (noisy-channel score, edit
+  costs, ...); `LayerPriority` only decides between suggestions of different
+  layers with the same score or, unscored, the same distance. A word found in both layers is returned once.
+*/
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::distance::DistanceMetric;
+use crate::loader::{read_dictionary, LoadOptions};
+use crate::lookup::{apply_verbosity, merge_order, Lookup};
+use crate::noisy::NoisyChannel;
+use crate::symspell::{Suggestion, SymSpell, Verbosity};
+
+/// Which layer wins when suggestions are at the same edit distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LayerPriority {
+    /// Rank by frequency, as if both layers were one dictionary.
+    #[default]
+    Frequency,
+    /// User words come before base words.
+    User,
+    /// Base words come before user words.
+    Base,
+}
+
+/// A read-only base dictionary with a mutable, persistable user layer.
+pub struct LayeredDictionary<B> {
+    base: B,
+    user: SymSpell,
+    user_frequency: usize,
+    priority: LayerPriority,
+    path: Option<PathBuf>,
+}
+
+impl<B: Lookup> LayeredDictionary<B> {
+    /// An empty user layer over `base`, not backed by a file.
+    pub fn new(base: B) -> Self {
+        let mut user =
+            SymSpell::new(base.max_distance()).with_shared_metric(base.distance_metric());
+        user.set_noisy_channel(base.noisy_channel());
+        Self {
+            base,
+            user,
+            user_frequency: 1,
+            priority: LayerPriority::default(),
+            path: None,
+        }
+    }
+
+    /// A user layer over `base` backed by the word list at `path`, which is
+    /// loaded if it exists and written by `save`.
+    pub fn open<P: AsRef<Path>>(base: B, path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut layered = Self::new(base);
+        if path.exists() {
+            let entries = read_dictionary(BufReader::new(File::open(path)?), &LoadOptions::new())?;
+            for (word, _) in entries {
+                layered.add_word(&word);
+            }
+        }
+        layered.path = Some(path.to_path_buf());
+        Ok(layered)
+    }
+
+    /// How suggestions at the same distance are ordered across layers.
+    pub fn with_priority(mut self, priority: LayerPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Current ordering of same-distance suggestions.
+    pub fn priority(&self) -> LayerPriority {
+        self.priority
+    }
+
+    /// Frequency given to user words, which ranks them against base words
+    /// under `LayerPriority::Frequency`. Applies to words already added.
+    pub fn with_user_frequency(mut self, frequency: usize) -> Self {
+        self.user_frequency = frequency;
+        let words: Vec<String> = self.user.words().map(|(w, _)| w.to_string()).collect();
+        self.user
+            .load_iter(words.into_iter().map(|w| (w, frequency)));
+        self
+    }
+
+    /// The read-only base dictionary.
+    pub fn base(&self) -> &B {
+        &self.base
+    }
+
+    /// The user layer.
+    pub fn user(&self) -> &SymSpell {
+        &self.user
+    }
+
+    /// File the user layer is saved to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Add `word`, lower-cased, to the user layer. Returns false if it was
+    /// already there.
+    pub fn add_word(&mut self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        if word.is_empty() || self.user.frequency(&word).is_some() {
+            return false;
+        }
+        self.user.load_iter([(word, self.user_frequency)]);
+        true
+    }
+
+    /// Remove `word` (in any case) from the user layer; base words cannot be
+    /// removed. Returns true if it was there.
+    pub fn remove_word(&mut self, word: &str) -> bool {
+        self.user.remove(&word.to_lowercase()).is_some()
+    }
+
+    /// Words of the user layer, sorted.
+    pub fn user_words(&self) -> Vec<&str> {
+        let mut words: Vec<&str> = self.user.words().map(|(w, _)| w).collect();
+        words.sort_unstable();
+        words
+    }
+
+    /// Write the user layer to the file it was opened from.
+    pub fn save(&self) -> io::Result<()> {
+        let path = self.path.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "user dictionary has no file; use save_to",
+            )
+        })?;
+        self.save_to(path)
+    }
+
+    /// Write the user layer to `path`, one word per line. The file is
+    /// replaced atomically, so readers never see a partial list.
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        {
+            let mut file = io::BufWriter::new(File::create(&tmp)?);
+            for word in self.user_words() {
+                writeln!(file, "{}", word)?;
+            }
+            file.flush()?;
+        }
+        fs::rename(&tmp, path)
+    }
+
+    /// Look up `term` in both layers and merge the suggestions.
+    pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        let base = self.base.lookup(term, max_distance, verbosity);
+        let user = self.user.lookup(term, max_distance, verbosity);
+        self.merge(base, user, verbosity)
+    }
+
+    /// Frequency of `word` in the user layer, else in the base.
+    pub fn frequency(&self, word: &str) -> Option<usize> {
+        self.user
+            .frequency(word)
+            .or_else(|| self.base.frequency(word))
+    }
+
+    /// Interleave the layers' suggestions, each already in its layer's order,
+    /// like the merge step of a merge sort.
+    fn merge(
+        &self,
+        base: Vec<Suggestion>,
+        user: Vec<Suggestion>,
+        verbosity: Verbosity,
+    ) -> Vec<Suggestion> {
+        let mut base = base.into_iter().peekable();
+        let mut user = user.into_iter().peekable();
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+        loop {
+            let from_user = match (user.peek(), base.peek()) {
+                (Some(u), Some(b)) => self.user_first(u, b),
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let next = if from_user { user.next() } else { base.next() };
+            if let Some(s) = next.filter(|s| seen.insert(s.term.clone())) {
+                merged.push(s);
+            }
+        }
+        apply_verbosity(&mut merged, verbosity);
+        merged
+    }
+
+    /// Whether the user suggestion `u` goes before the base suggestion `b`.
+    fn user_first(&self, u: &Suggestion, b: &Suggestion) -> bool {
+        merge_order(u, b)
+            .then(u.distance.cmp(&b.distance))
+            .then_with(|| match self.priority {
+                LayerPriority::Frequency => b
+                    .frequency
+                    .cmp(&u.frequency)
+                    .then_with(|| u.term.cmp(&b.term)),
+                LayerPriority::User => Ordering::Less,
+                LayerPriority::Base => Ordering::Greater,
+            })
+            .is_lt()
+    }
+}
+
+impl<B: Lookup> Lookup for LayeredDictionary<B> {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        LayeredDictionary::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.base.max_distance()
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        LayeredDictionary::frequency(self, word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        match (self.base.word_lengths(), self.user.word_lengths()) {
+            (Some((a, b)), Some((c, d))) => Some((a.min(c), b.max(d))),
+            (base, user) => base.or(user),
+        }
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.base.distance_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.base.noisy_channel()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::Origin;
+
+    fn base() -> SymSpell {
+        SymSpell::from_iter(2, [("hello", 100usize), ("help", 50), ("world", 80)])
+    }
+
+    #[test]
+    fn test_layered_lookup_and_priority() {
+        let mut dict = LayeredDictionary::new(base());
+        assert!(dict.add_word("helo"));
+        assert!(dict.add_word("symspellrs"));
+        assert!(!dict.add_word("helo"));
+
+        // Exact user word wins on distance regardless of priority.
+        assert_eq!(dict.lookup("helo", 2, Verbosity::Top)[0].term, "helo");
+        assert_eq!(
+            dict.lookup("symspelrs", 2, Verbosity::Top)[0].term,
+            "symspellrs"
+        );
+        assert!(dict.contains("symspellrs") && dict.contains("world"));
+
+        // "hell": hello/help (base) and helo (user) are all one edit away.
+        let terms = |d: &LayeredDictionary<SymSpell>| -> Vec<String> {
+            d.lookup("hell", 2, Verbosity::Closest)
+                .into_iter()
+                .map(|s| s.term)
+                .collect()
+        };
+        assert_eq!(terms(&dict), ["hello", "help", "helo"]);
+        let dict = dict.with_priority(LayerPriority::User);
+        assert_eq!(terms(&dict), ["helo", "hello", "help"]);
+        let dict = dict
+            .with_priority(LayerPriority::Frequency)
+            .with_user_frequency(75);
+        assert_eq!(terms(&dict), ["hello", "helo", "help"]);
+
+        let mut dict = dict.with_priority(LayerPriority::Base);
+        assert!(dict.remove_word("helo"));
+        assert!(!dict.remove_word("hello"));
+        assert_eq!(terms(&dict), ["hello", "help"]);
+        // A word in both layers is reported once.
+        dict.add_word("hello");
+        assert_eq!(dict.lookup("hello", 2, Verbosity::All)[0].term, "hello");
+        assert_eq!(
+            dict.lookup("hello", 2, Verbosity::All)
+                .iter()
+                .filter(|s| s.term == "hello")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_layered_keeps_base_order() {
+        use crate::corrections::CorrectionTable;
+        use crate::distance::{EditCosts, Levenshtein};
+        use crate::keyboard::KeyboardLayout;
+
+        let base = SymSpell::from_iter(2, [("test", 1usize), ("tent", 100), ("three", 10)])
+            .with_edit_costs(EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5))
+            .with_corrections([("thr", "three")].into_iter().collect::<CorrectionTable>());
+        let mut dict = LayeredDictionary::new(base).with_priority(LayerPriority::User);
+        assert!(dict.add_word("Thy"));
+        assert!(!dict.add_word("THY"));
+        assert_eq!(dict.user_words(), ["thy"]);
+
+        // The base's edit-cost order survives the merge...
+        let terms: Vec<String> = dict
+            .lookup("teat", 2, Verbosity::Closest)
+            .into_iter()
+            .map(|s| s.term)
+            .collect();
+        assert_eq!(terms, ["test", "tent"]);
+        // ...and its correction-table hit beats a closer user word.
+        let top = &dict.lookup("thr", 2, Verbosity::Top)[0];
+        assert_eq!(
+            (top.term.as_str(), top.origin),
+            ("three", Origin::CorrectionTable)
+        );
+        assert_eq!(dict.lookup("thr", 2, Verbosity::Closest).len(), 1);
+        let all = dict.lookup("thr", 2, Verbosity::All);
+        assert_eq!(all[0].term, "three");
+        assert!(all.iter().any(|s| s.term == "thy"));
+        assert!(dict.remove_word("Thy"));
+
+        // The user layer measures distances with the base's metric.
+        let base = SymSpell::from_iter(2, [("hello", 100usize)]).with_metric(Levenshtein);
+        let mut dict = LayeredDictionary::new(base);
+        dict.add_word("abcd");
+        assert_eq!(dict.lookup("abdc", 2, Verbosity::Top)[0].distance, 2);
+    }
+
+    #[test]
+    fn test_layered_noisy_channel() {
+        use crate::noisy::NoisyChannel;
+
+        let base =
+            SymSpell::from_iter(2, [("cart", 1000usize)]).with_noisy_channel(NoisyChannel::new());
+        let mut dict = LayeredDictionary::new(base).with_priority(LayerPriority::User);
+        dict.add_word("cat");
+        assert_eq!(dict.user().noisy_channel(), Some(NoisyChannel::new()));
+
+        // Both are one edit from "catr"; the scored base word outranks the
+        // rare user word despite `LayerPriority::User`.
+        let all = dict.lookup("catr", 2, Verbosity::All);
+        let terms: Vec<&str> = all.iter().map(|s| s.term.as_str()).collect();
+        assert_eq!(terms, ["cart", "cat"]);
+        assert!(all.iter().all(|s| s.score.is_some()));
+    }
+
+    #[test]
+    fn test_layered_persistence() {
+        let path = std::env::temp_dir().join(format!("symspellrs-user-{}.txt", std::process::id()));
+        fs::write(&path, "# my words\nzork\nfrobnicate\n").unwrap();
+
+        let mut dict = LayeredDictionary::open(base(), &path).unwrap();
+        assert_eq!(dict.user_words(), ["frobnicate", "zork"]);
+        dict.add_word("grault");
+        dict.remove_word("zork");
+        dict.save().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "frobnicate\ngrault\n");
+
+        let reopened = LayeredDictionary::open(base(), &path).unwrap();
+        assert_eq!(
+            reopened.lookup("grautl", 2, Verbosity::Top)[0].term,
+            "grault"
+        );
+        assert!(LayeredDictionary::new(base()).save().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -26,11 +26,175 @@
 //! let sym = SymSpell::from_iter(2, entries);
 //! let results = sym.lookup("helo", 2, Verbosity::Closest);
 //! ```
+//!
+//! - Choosing a distance metric (optimal string alignment is the default):
+//!
+//! ```ignore
+//! use symspellrs::{DamerauLevenshtein, SymSpell};
+//! let sym = SymSpell::from_iter(2, entries).with_metric(DamerauLevenshtein);
+//! ```
+//!
+//! - Ranking adjacent-key typos first within the same distance:
+//!
+//! ```ignore
+//! use symspellrs::{EditCosts, KeyboardLayout, SymSpell};
+//! let costs = EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5);
+//! let sym = SymSpell::from_iter(2, entries).with_edit_costs(costs);
+//! ```
+//!
+//! - Falling back to sound-alike words for badly misspelled input:
+//!
+//! ```ignore
+//! use symspellrs::{DoubleMetaphone, SymSpell, Verbosity};
+//! let sym = SymSpell::from_iter(2, entries).with_phonetic(DoubleMetaphone::default());
+//! let results = sym.lookup_phonetic("fonetik", 2, Verbosity::Top);
+//! ```
+//!
+//! - Correcting many tokens at once (enable the `parallel` feature to use rayon):
+//!
+//! ```ignore
+//! use symspellrs::{SymSpell, Verbosity};
+//! let results = sym.lookup_batch(&["helo", "wrld", "helo"], 2, Verbosity::Top);
+//! ```
+//!
+//! - Caching results of repeated queries:
+//!
+//! ```ignore
+//! use symspellrs::{CachedLookup, Verbosity};
+//! let cached = CachedLookup::new(sym, 10_000);
+//! let results = cached.lookup("helo", 2, Verbosity::Top);
+//! println!("hit rate: {:.2}", cached.stats().hit_rate());
+//! ```
+//!
+//! - Learning words while other threads look words up:
+//!
+//! ```ignore
+//! use symspellrs::SharedSymSpell;
+//! let shared = SharedSymSpell::new(sym);
+//! shared.add_words([("symspellrs", 1usize)]);
+//! let results = shared.lookup("symspelrs", 2, Verbosity::Top);
+//! ```
+//!
+//! - Reloading a frequency file when it changes on disk:
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use symspellrs::{LoadOptions, ReloadableSymSpell};
+//! let dict = ReloadableSymSpell::open("words.txt", 2, LoadOptions::new().with_freq(true))?
+//!     .watch(Duration::from_secs(5));
+//! let results = dict.lookup("helo", 2, Verbosity::Top);
+//! ```
+//!
+//! - Adding user words on top of a shipped (e.g. embedded) dictionary:
+//!
+//! ```ignore
+//! use symspellrs::{LayerPriority, LayeredDictionary};
+//! let mut dict = LayeredDictionary::open(embedded, "user-words.txt")?
+//!     .with_priority(LayerPriority::User);
+//! dict.add_word("symspellrs");
+//! dict.save()?;
+//! ```
+//!
+//! - Querying several dictionaries together, with weights and attribution:
+//!
+//! ```ignore
+//! use symspellrs::MultiDictionary;
+//! let multi = MultiDictionary::new()
+//!     .with_dictionary("english", english, 1.0)
+//!     .with_dictionary("glossary", glossary, 100.0);
+//! for s in multi.lookup("kubelt", 2, Verbosity::Closest) {
+//!     println!("{} ({:?})", s.term, s.dictionary);
+//! }
+//! ```
+//!
+//! - Checking a document:
+//!
+//! ```ignore
+//! for m in sym.check_text("Teh quick brown fox") {
+//!     println!("{}:{} {} -> {:?}", m.line, m.column, m.token, m.suggestions.first());
+//! }
+//! ```
+//!
+//! - Checking identifiers in source code:
+//!
+//! ```ignore
+//! let check = sym.check_identifier("parseHttpRespnse");
+//! assert_eq!(check.suggestions[0], "parseHttpResponse");
+//! ```
+//!
+//! - Correcting phrases with split or run-together words:
+//!
+//! ```ignore
+//! assert_eq!(sym.lookup_compound("whereis th elove", 2).term, "where is the love");
+//! assert_eq!(sym.word_segmentation("thequickbrownfox", 2).corrected, "the quick brown fox");
+//! ```
+//!
+//! - Ranking candidates by the surrounding words with bigram counts:
+//!
+//! ```ignore
+//! use symspellrs::{include_bigram_dictionary, ContextualLookup};
+//! let dict = ContextualLookup::new(sym, include_bigram_dictionary!("bigrams.txt"));
+//! let top = dict.lookup_context("frm", Some("came"), None, 2, Verbosity::Top);
+//! assert_eq!(top[0].term, "from");
+//! ```
+//!
+//! - Exporting results as JSON Lines or SARIF:
+//!
+//! ```ignore
+//! use symspellrs::{write_json_lines, SarifReport};
+//! let found: Vec<_> = sym.check_text(&text).collect();
+//! write_json_lines(&mut std::io::stdout(), "notes.txt", &found)?;
+//! let mut sarif = SarifReport::new();
+//! sarif.add("notes.txt", &found);
+//! sarif.write(&mut std::io::stdout())?;
+//! ```
 
+pub mod bigram;
+pub mod blocklist;
+pub mod cache;
+pub mod compound;
+pub mod corrections;
+pub mod distance;
+pub mod identifier;
+pub mod keyboard;
+pub mod layered;
+pub mod loader;
+pub mod lookup;
+pub mod multi;
+pub mod noisy;
+pub mod phonetic;
+pub mod reload;
+pub mod report;
+pub mod shared;
 pub mod symspell;
+pub mod text;
+pub mod tokenize;
 
 /// Re-export commonly used types from the `symspell` module.
-pub use symspell::{EmbeddedSymSpell, Suggestion, SymSpell, Verbosity};
+pub use symspell::{EmbeddedSymSpell, Fallback, Origin, Suggestion, SymSpell, Verbosity};
+
+pub use bigram::{BigramDictionary, ContextualLookup};
+pub use blocklist::Blocklist;
+pub use cache::{CacheStats, CachedLookup};
+pub use compound::Segmentation;
+pub use corrections::CorrectionTable;
+/// Re-export the distance metrics used to verify candidates.
+pub use distance::{
+    DamerauLevenshtein, DistanceMetric, EditCosts, Levenshtein, OptimalStringAlignment,
+};
+pub use identifier::{CaseStyle, IdentifierCheck, IdentifierTokenizer, SubwordMisspelling};
+pub use keyboard::KeyboardLayout;
+pub use layered::{LayerPriority, LayeredDictionary};
+pub use loader::LoadOptions;
+pub use lookup::Lookup;
+pub use multi::MultiDictionary;
+pub use noisy::NoisyChannel;
+pub use phonetic::{DoubleMetaphone, PhoneticEncoder, Soundex};
+pub use reload::ReloadableSymSpell;
+pub use report::{misspelling_json, write_json_lines, SarifReport};
+pub use shared::SharedSymSpell;
+pub use text::{CheckOptions, CheckText, Misspelling};
+pub use tokenize::{IgnoreRule, Tokenizer, WordTokenizer};
 
 /// Re-export the compile-time dictionary macro from the proc-macro crate.
 ///
@@ -40,3 +204,9 @@ pub use symspell::{EmbeddedSymSpell, Suggestion, SymSpell, Verbosity};
 ///
 ///   use symspellrs::include_dictionary;
 pub use symspellrs_macros::include_dictionary;
+
+/// Re-export the compile-time bigram dictionary macro, which returns a
+/// `BigramDictionary` for `ContextualLookup`:
+///
+///   use symspellrs::include_bigram_dictionary;
+pub use symspellrs_macros::include_bigram_dictionary;
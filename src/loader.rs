@@ -0,0 +1,156 @@
+/*!
+loader module
+
+Runtime counterpart of `include_dictionary!`: reads dictionary files in the
+same format, one entry per line:
+
+- blank lines and lines starting with `#` are skipped;
+- without frequencies each line is a word (frequency 1);
+- with `has_freq` each line is `word frequency`, separated by whitespace.
+
+Repeated words have their frequencies summed.
+*/
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::symspell::SymSpell;
+
+/// How to read a dictionary file (mirrors the `include_dictionary!` options).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoadOptions {
+    /// Lines are `word frequency` instead of a bare word.
+    pub has_freq: bool,
+    /// Lower-case words before inserting them.
+    pub lowercase: bool,
+}
+
+impl LoadOptions {
+    /// Bare words, case preserved.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a frequency after each word.
+    pub fn with_freq(mut self, has_freq: bool) -> Self {
+        self.has_freq = has_freq;
+        self
+    }
+
+    /// Lower-case words before inserting them.
+    pub fn with_lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+}
+
+/// Read `(word, frequency)` entries from `reader`.
+///
+/// Malformed lines are reported as `io::ErrorKind::InvalidData` with their
+/// line number.
+pub fn read_dictionary<R: BufRead>(
+    reader: R,
+    options: &LoadOptions,
+) -> io::Result<Vec<(String, usize)>> {
+    let mut order: Vec<String> = Vec::new();
+    let mut freqs: HashMap<String, usize> = HashMap::new();
+    for (lineno, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let invalid = |what: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}: {}", lineno + 1, what, trimmed),
+            )
+        };
+        let (word, freq) = if options.has_freq {
+            let mut parts = trimmed.split_whitespace();
+            let word = parts.next().ok_or_else(|| invalid("expected word"))?;
+            let freq = parts
+                .next()
+                .ok_or_else(|| invalid("expected frequency"))?
+                .parse::<usize>()
+                .map_err(|_| invalid("invalid frequency"))?;
+            (word, freq)
+        } else {
+            (trimmed, 1)
+        };
+        let word = if options.lowercase {
+            word.to_lowercase()
+        } else {
+            word.to_string()
+        };
+        match freqs.get_mut(&word) {
+            Some(total) => *total += freq,
+            None => {
+                freqs.insert(word.clone(), freq);
+                order.push(word);
+            }
+        }
+    }
+    Ok(order
+        .into_iter()
+        .map(|word| {
+            let freq = freqs[&word];
+            (word, freq)
+        })
+        .collect())
+}
+
+impl SymSpell {
+    /// Build a `SymSpell` from a dictionary file (see the `loader` module for
+    /// the format).
+    pub fn from_file<P: AsRef<Path>>(
+        max_distance: u8,
+        path: P,
+        options: &LoadOptions,
+    ) -> io::Result<Self> {
+        let mut sym = SymSpell::new(max_distance);
+        sym.load_file(path, options)?;
+        Ok(sym)
+    }
+
+    /// Load entries from a dictionary file, inserting or updating words.
+    /// Nothing is inserted if the file cannot be read or parsed.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P, options: &LoadOptions) -> io::Result<()> {
+        let entries = read_dictionary(BufReader::new(File::open(path)?), options)?;
+        self.load_iter(entries);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_dictionary() {
+        let text = "# comment\nHello 3\n\nworld 2\nhello 4\n";
+        let opts = LoadOptions::new().with_freq(true).with_lowercase(true);
+        let entries = read_dictionary(text.as_bytes(), &opts).unwrap();
+        assert_eq!(
+            entries,
+            vec![("hello".to_string(), 7), ("world".to_string(), 2)]
+        );
+
+        let words = read_dictionary("one\ntwo\n".as_bytes(), &LoadOptions::new()).unwrap();
+        assert_eq!(words, vec![("one".to_string(), 1), ("two".to_string(), 1)]);
+
+        let err = read_dictionary("ok 1\nbad x\n".as_bytes(), &opts).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_from_file() {
+        let sym = SymSpell::from_file(2, "tests/data/words.txt", &LoadOptions::new()).unwrap();
+        assert_eq!(sym.frequency("hello"), Some(1));
+        assert_eq!(sym.len(), 19);
+        assert!(SymSpell::from_file(2, "tests/data/missing.txt", &LoadOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+/*!
+lookup module
+
+The `Lookup` trait abstracts over `SymSpell` and `EmbeddedSymSpell` so that
+callers (and wrappers around them) can work with either, and provides batch
+lookups for correcting many tokens at once, plus the text, identifier and
+compound checks built on top of `lookup`.
+
+`lookup_batch` looks up each distinct term once and copies the result to its
+repeats. With the `parallel` cargo feature the distinct terms are spread over
+rayon's global thread pool.
+*/
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::compound::{lookup_compound, word_segmentation, Segmentation};
+use crate::distance::{DistanceMetric, OptimalStringAlignment};
+use crate::identifier::{check_identifier, IdentifierCheck};
+use crate::noisy::NoisyChannel;
+use crate::symspell::{EmbeddedSymSpell, Origin, Suggestion, SymSpell, Verbosity};
+use crate::text::{CheckOptions, CheckText};
+
+/// A dictionary that can be queried for spelling suggestions.
+pub trait Lookup: Sync {
+    /// Look up suggestions for `term`; see `SymSpell::lookup`.
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion>;
+
+    /// Maximum edit distance the index was built for.
+    fn max_distance(&self) -> u8;
+
+    /// Frequency of `word`, or `None` if it is not in the dictionary.
+    fn frequency(&self, word: &str) -> Option<usize>;
+
+    /// Returns true if `word` is in the dictionary.
+    fn contains(&self, word: &str) -> bool {
+        self.frequency(word).is_some()
+    }
+
+    /// Length in characters of the shortest and longest dictionary word, if
+    /// known. Bounds segment lengths in `word_segmentation`.
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Metric suggestion distances are measured with, so wrappers can build
+    /// indexes that agree with this one. Optimal string alignment by default.
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        Arc::new(OptimalStringAlignment)
+    }
+
+    /// Noisy-channel settings suggestions are scored with, if any (see
+    /// `Suggestion::score`).
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        None
+    }
+
+    /// Look up every term of `terms`, returning one suggestion list per term
+    /// in the same order.
+    fn lookup_batch<S>(
+        &self,
+        terms: &[S],
+        max_distance: u8,
+        verbosity: Verbosity,
+    ) -> Vec<Vec<Suggestion>>
+    where
+        S: AsRef<str> + Sync,
+        Self: Sized,
+    {
+        lookup_batch(self, terms, max_distance, verbosity)
+    }
+
+    /// Iterate over the words of `text` that are not in the dictionary, with
+    /// their positions and suggestions (see the `text` module).
+    fn check_text<'a>(&'a self, text: &'a str) -> CheckText<'a, Self>
+    where
+        Self: Sized,
+    {
+        CheckText::new(self, text, CheckOptions::default())
+    }
+
+    /// Split a source-code identifier into subwords, check each one and
+    /// suggest corrected identifiers in the same naming convention (see the
+    /// `identifier` module).
+    fn check_identifier(&self, identifier: &str) -> IdentifierCheck {
+        check_identifier(self, identifier, self.max_distance())
+    }
+
+    /// Correct a phrase whose words may be misspelled, split apart or run
+    /// together, returning one suggestion for the whole phrase (see the
+    /// `compound` module).
+    fn lookup_compound(&self, input: &str, max_distance: u8) -> Suggestion {
+        lookup_compound(self, input, max_distance, None)
+    }
+
+    /// Insert missing spaces into `input` and correct each segment (see the
+    /// `compound` module).
+    fn word_segmentation(&self, input: &str, max_distance: u8) -> Segmentation {
+        word_segmentation(self, input, max_distance)
+    }
+
+    /// `check_text` with explicit options.
+    fn check_text_with<'a>(&'a self, text: &'a str, options: CheckOptions) -> CheckText<'a, Self>
+    where
+        Self: Sized,
+    {
+        CheckText::new(self, text, options)
+    }
+}
+
+/// Cut suggestions merged from several sources, sorted best first, down to
+/// what `verbosity` asks for. A leading correction-table hit is, as in a
+/// single dictionary, the only `Closest` suggestion.
+pub(crate) fn apply_verbosity(suggestions: &mut Vec<Suggestion>, verbosity: Verbosity) {
+    match verbosity {
+        Verbosity::Top => suggestions.truncate(1),
+        Verbosity::Closest => match suggestions.first() {
+            Some(s) if s.origin == Origin::CorrectionTable => suggestions.truncate(1),
+            _ => {
+                if let Some(closest) = suggestions.iter().map(|s| s.distance).min() {
+                    suggestions.retain(|s| s.distance == closest);
+                }
+            }
+        },
+        Verbosity::All => {}
+    }
+}
+
+/// Order of suggestions merged from several lists: correction-table hits
+/// first, then the higher noisy-channel `score` when both are scored. Ties
+/// are left to the caller's next criterion.
+pub(crate) fn merge_order(a: &Suggestion, b: &Suggestion) -> Ordering {
+    let uncorrected = |s: &Suggestion| s.origin != Origin::CorrectionTable;
+    uncorrected(a)
+        .cmp(&uncorrected(b))
+        .then_with(|| match (a.score, b.score) {
+            (Some(sa), Some(sb)) => sb.total_cmp(&sa),
+            _ => Ordering::Equal,
+        })
+}
+
+/// Shared implementation of `Lookup::lookup_batch`.
+fn lookup_batch<L, S>(
+    dictionary: &L,
+    terms: &[S],
+    max_distance: u8,
+    verbosity: Verbosity,
+) -> Vec<Vec<Suggestion>>
+where
+    L: Lookup,
+    S: AsRef<str> + Sync,
+{
+    // Position of each term's results within `distinct`.
+    let mut slots: HashMap<&str, usize> = HashMap::new();
+    let mut distinct: Vec<&str> = Vec::new();
+    let positions: Vec<usize> = terms
+        .iter()
+        .map(|term| {
+            let term = term.as_ref();
+            *slots.entry(term).or_insert_with(|| {
+                distinct.push(term);
+                distinct.len() - 1
+            })
+        })
+        .collect();
+
+    #[cfg(feature = "parallel")]
+    let results: Vec<Vec<Suggestion>> = distinct
+        .par_iter()
+        .map(|term| dictionary.lookup(term, max_distance, verbosity))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<Vec<Suggestion>> = distinct
+        .iter()
+        .map(|term| dictionary.lookup(term, max_distance, verbosity))
+        .collect();
+
+    positions.into_iter().map(|i| results[i].clone()).collect()
+}
+
+impl Lookup for SymSpell {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        SymSpell::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        SymSpell::max_distance(self)
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        SymSpell::frequency(self, word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        SymSpell::word_lengths(self)
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.shared_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        SymSpell::noisy_channel(self)
+    }
+}
+
+impl Lookup for EmbeddedSymSpell {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        EmbeddedSymSpell::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.max_distance
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        EmbeddedSymSpell::frequency(self, word)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        EmbeddedSymSpell::contains(self, word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        EmbeddedSymSpell::word_lengths(self)
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.shared_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        EmbeddedSymSpell::noisy_channel(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_batch() {
+        let entries = vec![
+            ("hello".to_string(), 100usize),
+            ("help".to_string(), 10usize),
+            ("world".to_string(), 200usize),
+        ];
+        let sym = SymSpell::from_iter(2, entries);
+        let terms = ["helo", "wrld", "helo", "", "xyzzyq", "wrld"];
+
+        let batch = sym.lookup_batch(&terms, 2, Verbosity::Top);
+        assert_eq!(batch.len(), terms.len());
+        for (term, suggestions) in terms.iter().zip(&batch) {
+            assert_eq!(suggestions, &sym.lookup(term, 2, Verbosity::Top));
+        }
+        assert_eq!(batch[0][0].term, "hello");
+        assert_eq!(batch[1][0].term, "world");
+        assert!(batch[3].is_empty() && batch[4].is_empty());
+
+        // Owned strings work as well, through the trait.
+        let owned: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        assert_eq!(Lookup::lookup_batch(&sym, &owned, 2, Verbosity::Top), batch);
+        assert!(Lookup::contains(&sym, "help"));
+    }
+}
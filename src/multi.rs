@@ -0,0 +1,353 @@
+/*!
+multi module
+
+`MultiDictionary` queries several named dictionaries together (say a general
+English dictionary, a domain glossary and a list of product names) and merges
+their suggestions:
+
+- each dictionary has a weight. Suggestion frequencies are multiplied by it,
+  which makes corpora of different sizes comparable and lets small curated
+  lists outrank a large general one;
+- correction-table hits come first. Other suggestions are ranked by
+  distance, then by their rank within their own dictionary (so its edit-cost
+  order is kept), then by weighted frequency;
+- if every dictionary scores with the same `NoisyChannel` (which
+  `Lookup::noisy_channel` then reports), suggestions are ranked by
+  `Suggestion::score` first. Scores are recomputed for the weighted frequency
+  (`log10(weight)` is added), so weights apply to them too. Otherwise scores
+  are not comparable and suggestions are returned unscored;
+- a term found in several dictionaries is returned once, from the dictionary
+  where it ranks best;
+- `Suggestion::dictionary` names the dictionary each suggestion came from.
+
+Dictionaries are any `Lookup` (`SymSpell`, `EmbeddedSymSpell`, the wrappers
+around them, or a `LayeredDictionary`).
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::distance::{DistanceMetric, OptimalStringAlignment};
+use crate::lookup::{apply_verbosity, merge_order, Lookup};
+use crate::noisy::NoisyChannel;
+use crate::symspell::{Suggestion, Verbosity};
+
+struct Source {
+    name: Arc<str>,
+    dictionary: Box<dyn Lookup + Send>,
+    weight: f64,
+}
+
+/// Several named, weighted dictionaries queried as one.
+#[derive(Default)]
+pub struct MultiDictionary {
+    sources: Vec<Source>,
+}
+
+impl MultiDictionary {
+    /// A multi-dictionary with no dictionaries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `dictionary` under `name` with frequency multiplier `weight`.
+    ///
+    /// # Panics
+    ///
+    /// If `weight` is negative or not finite.
+    pub fn with_dictionary<L>(mut self, name: &str, dictionary: L, weight: f64) -> Self
+    where
+        L: Lookup + Send + 'static,
+    {
+        self.add(name, dictionary, weight);
+        self
+    }
+
+    /// Add `dictionary` under `name` with frequency multiplier `weight`
+    /// (see `with_dictionary`).
+    pub fn add<L>(&mut self, name: &str, dictionary: L, weight: f64)
+    where
+        L: Lookup + Send + 'static,
+    {
+        assert!(
+            weight.is_finite() && weight >= 0.0,
+            "dictionary weight must be finite and non-negative, got {}",
+            weight
+        );
+        self.sources.push(Source {
+            name: Arc::from(name),
+            dictionary: Box::new(dictionary),
+            weight,
+        });
+    }
+
+    /// Number of dictionaries.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns true if no dictionary was added.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Names and weights of the dictionaries, in the order they were added.
+    pub fn dictionaries(&self) -> impl Iterator<Item = (&str, f64)> + '_ {
+        self.sources.iter().map(|s| (&*s.name, s.weight))
+    }
+
+    /// Look up `term` in every dictionary and merge the suggestions.
+    /// `max_distance` is capped by each dictionary's own maximum.
+    pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        // Each suggestion with its rank among its dictionary's suggestions at
+        // the same distance.
+        let mut all: Vec<(usize, Suggestion)> = Vec::new();
+        let scored = Lookup::noisy_channel(self).is_some();
+        for source in &self.sources {
+            let max_distance = max_distance.min(source.dictionary.max_distance());
+            let mut ranks: HashMap<u8, usize> = HashMap::new();
+            for mut s in source.dictionary.lookup(term, max_distance, verbosity) {
+                let rank = ranks.entry(s.distance).or_default();
+                s.frequency = weighted(s.frequency, source.weight);
+                s.score = s
+                    .score
+                    .filter(|_| scored)
+                    .map(|score| score + source.weight.log10());
+                s.dictionary = Some(Arc::clone(&source.name));
+                all.push((*rank, s));
+                *rank += 1;
+            }
+        }
+        // Stable: equally ranked suggestions keep the order dictionaries were added in.
+        all.sort_by(|(ra, a), (rb, b)| {
+            merge_order(a, b)
+                .then(a.distance.cmp(&b.distance))
+                .then(ra.cmp(rb))
+                .then(b.frequency.cmp(&a.frequency))
+        });
+        let mut seen = HashSet::new();
+        let mut merged: Vec<Suggestion> = all
+            .into_iter()
+            .map(|(_, s)| s)
+            .filter(|s| seen.insert(s.term.clone()))
+            .collect();
+        apply_verbosity(&mut merged, verbosity);
+        merged
+    }
+
+    /// Highest weighted frequency of `word` across the dictionaries, or
+    /// `None` if none contains it.
+    pub fn frequency(&self, word: &str) -> Option<usize> {
+        self.sources
+            .iter()
+            .filter_map(|s| Some(weighted(s.dictionary.frequency(word)?, s.weight)))
+            .max()
+    }
+
+    /// Name of the first dictionary (in the order added) containing `word`.
+    pub fn dictionary_of(&self, word: &str) -> Option<&str> {
+        self.sources
+            .iter()
+            .find(|s| s.dictionary.contains(word))
+            .map(|s| &*s.name)
+    }
+}
+
+fn weighted(frequency: usize, weight: f64) -> usize {
+    (frequency as f64 * weight).round() as usize
+}
+
+impl Lookup for MultiDictionary {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        MultiDictionary::lookup(self, term, max_distance, verbosity)
+    }
+
+    /// The largest `max_distance` of the dictionaries.
+    fn max_distance(&self) -> u8 {
+        self.sources
+            .iter()
+            .map(|s| s.dictionary.max_distance())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        MultiDictionary::frequency(self, word)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.sources.iter().any(|s| s.dictionary.contains(word))
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.sources
+            .iter()
+            .filter_map(|s| s.dictionary.word_lengths())
+            .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))
+    }
+
+    /// The first dictionary's metric: distances from the others are only
+    /// comparable if they use the same one.
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        match self.sources.first() {
+            Some(source) => source.dictionary.distance_metric(),
+            None => Arc::new(OptimalStringAlignment),
+        }
+    }
+
+    /// The noisy channel shared by every dictionary, if they all use the same.
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        let mut channels = self.sources.iter().map(|s| s.dictionary.noisy_channel());
+        let first = channels.next()??;
+        channels.all(|c| c == Some(first)).then_some(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::{Origin, SymSpell};
+
+    fn multi() -> MultiDictionary {
+        let english =
+            SymSpell::from_iter(2, [("cloud", 5000usize), ("clout", 900), ("kublet", 40)]);
+        let glossary = SymSpell::from_iter(2, [("kubelet", 3usize), ("cloud", 2)]);
+        let products = SymSpell::from_iter(1, [("clowd", 1usize)]);
+        MultiDictionary::new()
+            .with_dictionary("english", english, 1.0)
+            .with_dictionary("glossary", glossary, 1000.0)
+            .with_dictionary("products", products, 500.0)
+    }
+
+    #[test]
+    fn test_multi_lookup() {
+        let multi = multi();
+        assert_eq!(multi.len(), 3);
+
+        let all = multi.lookup("clowt", 2, Verbosity::All);
+        let found: Vec<(&str, usize, Option<&str>)> = all
+            .iter()
+            .map(|s| (s.term.as_str(), s.frequency, s.dictionary.as_deref()))
+            .collect();
+        // "cloud" is in two dictionaries; the glossary's weighted 2000 loses to
+        // english's 5000, so it is reported once, from english.
+        assert_eq!(
+            found,
+            [
+                ("clout", 900, Some("english")),
+                ("clowd", 500, Some("products")),
+                ("cloud", 5000, Some("english")),
+            ]
+        );
+
+        // The glossary weight lifts a rare term (3) over a more common one (40)
+        // at equal distance.
+        let top = multi.lookup("kubelt", 2, Verbosity::Top);
+        assert_eq!(top[0].term, "kubelet");
+        assert_eq!(top[0].dictionary.as_deref(), Some("glossary"));
+        assert_eq!(top[0].frequency, 3000);
+
+        let closest = multi.lookup("clout", 2, Verbosity::Closest);
+        assert_eq!(closest.len(), 1);
+        assert_eq!(closest[0].distance, 0);
+
+        assert_eq!(multi.frequency("cloud"), Some(5000));
+        assert_eq!(multi.dictionary_of("kubelet"), Some("glossary"));
+        assert!(Lookup::contains(&multi, "clowd"));
+        assert_eq!(Lookup::max_distance(&multi), 2);
+        assert_eq!(Lookup::word_lengths(&multi), Some((5, 7)));
+    }
+
+    #[test]
+    fn test_multi_keeps_source_order() {
+        use crate::corrections::CorrectionTable;
+        use crate::distance::EditCosts;
+        use crate::keyboard::KeyboardLayout;
+
+        let typed = SymSpell::from_iter(2, [("test", 1usize), ("tent", 100), ("thy", 900)])
+            .with_edit_costs(EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5));
+        let corrected = SymSpell::from_iter(2, [("teal", 50usize), ("three", 1)])
+            .with_corrections([("thr", "three")].into_iter().collect::<CorrectionTable>());
+        let multi = MultiDictionary::new()
+            .with_dictionary("typed", typed, 1.0)
+            .with_dictionary("corrected", corrected, 1.0);
+
+        // "tent" is more frequent, but its dictionary ranks "test" first.
+        let terms: Vec<String> = multi
+            .lookup("teat", 2, Verbosity::Closest)
+            .into_iter()
+            .map(|s| s.term)
+            .collect();
+        assert_eq!(terms, ["teal", "test", "tent"]);
+
+        // A correction-table hit beats a closer, more frequent candidate.
+        let top = &multi.lookup("thr", 2, Verbosity::Top)[0];
+        assert_eq!(
+            (top.term.as_str(), top.origin),
+            ("three", Origin::CorrectionTable)
+        );
+        assert_eq!(multi.lookup("thr", 2, Verbosity::All)[0].term, "three");
+    }
+
+    #[test]
+    fn test_multi_noisy_channel() {
+        use crate::noisy::NoisyChannel;
+
+        let noisy = NoisyChannel::new();
+        let multi = MultiDictionary::new()
+            .with_dictionary(
+                "english",
+                SymSpell::from_iter(2, [("cart", 1000usize)]).with_noisy_channel(noisy),
+                1.0,
+            )
+            .with_dictionary(
+                "glossary",
+                SymSpell::from_iter(2, [("cat", 10usize)]).with_noisy_channel(noisy),
+                1000.0,
+            );
+        assert_eq!(Lookup::noisy_channel(&multi), Some(noisy));
+
+        // Scores account for the weight: the glossary's 10 counts as 10000.
+        let top = &multi.lookup("catr", 2, Verbosity::Top)[0];
+        assert_eq!(top.term, "cat");
+        assert!((top.score.unwrap() - noisy.score(10_000, 1.0)).abs() < 1e-9);
+
+        // With an unscored dictionary scores are not comparable: none is kept.
+        let mixed =
+            multi.with_dictionary("plain", SymSpell::from_iter(2, [("carts", 1usize)]), 1.0);
+        assert_eq!(Lookup::noisy_channel(&mixed), None);
+        let all = mixed.lookup("catr", 2, Verbosity::All);
+        assert!(all.len() == 3 && all.iter().all(|s| s.score.is_none()));
+    }
+
+    #[test]
+    fn test_wrappers_over_multi_keep_noisy_ranking() {
+        use crate::bigram::{BigramDictionary, ContextualLookup};
+        use crate::distance::Levenshtein;
+        use crate::layered::LayeredDictionary;
+        use crate::noisy::NoisyChannel;
+
+        // Noisy-channel ranking prefers "the", two edits from "thewe".
+        let multi = || {
+            let english = SymSpell::from_iter(2, [("thew", 5usize), ("the", 100_000)])
+                .with_metric(Levenshtein)
+                .with_noisy_channel(NoisyChannel::new());
+            MultiDictionary::new().with_dictionary("english", english, 1.0)
+        };
+        let contextual = ContextualLookup::new(multi(), BigramDictionary::new());
+        let top = contextual.lookup_context("thewe", Some("of"), None, 2, Verbosity::Top);
+        assert_eq!(top[0].term, "the");
+
+        let mut layered = LayeredDictionary::new(multi());
+        layered.add_word("thewa");
+        assert_eq!(layered.lookup("thewe", 2, Verbosity::Top)[0].term, "the");
+        // The user layer measures with the dictionaries' metric too.
+        assert_eq!(layered.user().metric().distance("ab", "ba"), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "weight")]
+    fn test_multi_rejects_bad_weight() {
+        MultiDictionary::new().with_dictionary("x", SymSpell::new(2), f64::NAN);
+    }
+}
@@ -0,0 +1,131 @@
+/*!
+noisy module
+
+Noisy-channel ranking. By default suggestions are ranked distance first, so a
+very common word two edits away never beats an obscure one a single edit
+away. With `NoisyChannel` scoring (`SymSpell::with_noisy_channel`,
+`EmbeddedSymSpell::with_noisy_channel`) each candidate `c` for a query `t` is
+scored by
+
+```text
+log10 P(t | c) + log10 P(c)
+```
+
+- the error model `P(t | c) = edit_probability ^ edits`, where `edits` is
+  the edit distance, or the weighted edit cost when `EditCosts` are
+  configured (so cheap adjacent-key slips cost less);
+- the language-model prior `P(c) = frequency / corpus_size`, from the
+  dictionary counts.
+
+Suggestions are then ordered by score, which `Suggestion::score` exposes.
+With `edit_probability = 0.01`, one extra edit is worth a hundredfold
+frequency. `Verbosity::Top` returns the best-scoring candidate within
+`max_distance`; `Closest` still returns the candidates at the smallest
+distance, ordered by score.
+*/
+
+use crate::compound::CORPUS_SIZE;
+
+/// Error-model and prior settings for noisy-channel ranking.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoisyChannel {
+    /// Probability of each edit (typo) in the error model, in `(0, 1)`.
+    pub edit_probability: f64,
+    /// Total count the dictionary frequencies are relative to.
+    pub corpus_size: f64,
+}
+
+impl Default for NoisyChannel {
+    fn default() -> Self {
+        Self {
+            edit_probability: 0.01,
+            corpus_size: CORPUS_SIZE,
+        }
+    }
+}
+
+impl NoisyChannel {
+    /// Edit probability 0.01 over a `CORPUS_SIZE`-word corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Probability of each edit in the error model.
+    ///
+    /// # Panics
+    ///
+    /// If `probability` is not strictly between 0 and 1.
+    pub fn with_edit_probability(mut self, probability: f64) -> Self {
+        assert!(
+            probability > 0.0 && probability < 1.0,
+            "edit probability must be in (0, 1), got {}",
+            probability
+        );
+        self.edit_probability = probability;
+        self
+    }
+
+    /// Total count the dictionary frequencies are relative to (only shifts
+    /// scores, never their order).
+    ///
+    /// # Panics
+    ///
+    /// If `size` is not positive and finite.
+    pub fn with_corpus_size(mut self, size: f64) -> Self {
+        assert!(
+            size.is_finite() && size > 0.0,
+            "corpus size must be positive, got {}",
+            size
+        );
+        self.corpus_size = size;
+        self
+    }
+
+    /// Log10 probability of a candidate with `frequency`, `edits` edits away
+    /// from the query. Frequencies of 0 count as 1.
+    pub fn score(&self, frequency: usize, edits: f64) -> f64 {
+        edits * self.edit_probability.log10() + (frequency.max(1) as f64 / self.corpus_size).log10()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::{SymSpell, Verbosity};
+
+    #[test]
+    fn test_noisy_channel_ranking() {
+        let entries = [("thew", 5usize), ("the", 100_000), ("then", 800)];
+        let plain = SymSpell::from_iter(2, entries);
+        let noisy = SymSpell::from_iter(2, entries).with_noisy_channel(NoisyChannel::new());
+
+        // Distance first: the obscure word one edit away wins.
+        let top = &plain.lookup("thewe", 2, Verbosity::Top)[0];
+        assert_eq!((top.term.as_str(), top.score), ("thew", None));
+        // Noisy channel: 20000x the frequency outweighs the extra edit...
+        let top = &noisy.lookup("thewe", 2, Verbosity::Top)[0];
+        assert_eq!((top.term.as_str(), top.distance), ("the", 2));
+        let expected = 2.0 * 0.01f64.log10() + (100_000.0 / CORPUS_SIZE).log10();
+        assert!((top.score.unwrap() - expected).abs() < 1e-9);
+
+        // ...but not with a stricter error model.
+        let strict = SymSpell::from_iter(2, entries)
+            .with_noisy_channel(NoisyChannel::new().with_edit_probability(1e-6));
+        assert_eq!(strict.lookup("thewe", 2, Verbosity::Top)[0].term, "thew");
+
+        // All is ordered by score; Closest keeps the smallest distance.
+        let all = noisy.lookup("thewe", 2, Verbosity::All);
+        let scores: Vec<f64> = all.iter().map(|s| s.score.unwrap()).collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(all[0].term, "the");
+        let closest = noisy.lookup("thewe", 2, Verbosity::Closest);
+        assert_eq!(closest.len(), 1);
+        assert_eq!(closest[0].term, "thew");
+    }
+
+    #[test]
+    #[should_panic(expected = "edit probability")]
+    fn test_noisy_channel_rejects_bad_probability() {
+        NoisyChannel::new().with_edit_probability(1.0);
+    }
+}
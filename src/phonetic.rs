@@ -0,0 +1,737 @@
+/*!
+phonetic module
+
+Phonetic encoders used to build an optional sound-alike index next to the
+deletion index (see `SymSpell::with_phonetic` and `SymSpell::lookup_phonetic`).
+
+- `Soundex`: American Soundex (letter + three digits). Cheap and coarse.
+- `DoubleMetaphone`: Lawrence Philips' Double Metaphone, returning a primary
+  and (when it differs) an alternate code. Much better for English and names.
+
+Encoders only consider ASCII letters (plus `Ç` and `Ñ` for Double Metaphone);
+other characters are ignored. Words that yield no code are not indexed.
+*/
+
+/// Encodes a word into one or more phonetic codes.
+///
+/// Two words "sound alike" when they share at least one code.
+pub trait PhoneticEncoder: Send + Sync {
+    /// Phonetic codes for `word`, most likely first. Empty if the word has none.
+    fn encode(&self, word: &str) -> Vec<String>;
+}
+
+/// American Soundex.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Soundex;
+
+impl Soundex {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'B' | 'F' | 'P' | 'V' => Some('1'),
+            'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+            'D' | 'T' => Some('3'),
+            'L' => Some('4'),
+            'M' | 'N' => Some('5'),
+            'R' => Some('6'),
+            _ => None,
+        }
+    }
+}
+
+impl PhoneticEncoder for Soundex {
+    fn encode(&self, word: &str) -> Vec<String> {
+        let mut letters = word
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase());
+        let Some(first) = letters.next() else {
+            return Vec::new();
+        };
+
+        let mut code = String::with_capacity(4);
+        code.push(first);
+        let mut last = Self::digit(first);
+        for c in letters {
+            let d = Self::digit(c);
+            if d.is_some() && d != last {
+                code.push(d.unwrap_or('0'));
+                if code.len() == 4 {
+                    break;
+                }
+            }
+            // 'H' and 'W' do not separate letters with the same code; vowels do.
+            if c != 'H' && c != 'W' {
+                last = d;
+            }
+        }
+        while code.len() < 4 {
+            code.push('0');
+        }
+        vec![code]
+    }
+}
+
+/// Double Metaphone with a configurable maximum code length (4 by default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoubleMetaphone {
+    max_len: usize,
+}
+
+impl Default for DoubleMetaphone {
+    fn default() -> Self {
+        Self { max_len: 4 }
+    }
+}
+
+impl DoubleMetaphone {
+    /// Encoder producing codes of at most `max_len` characters.
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self { max_len }
+    }
+
+    /// Primary and alternate codes for `word` (equal when there is no alternate).
+    pub fn encode_pair(&self, word: &str) -> (String, String) {
+        let value: Vec<char> = word.trim().chars().flat_map(|c| c.to_uppercase()).collect();
+        let mut enc = Encoder {
+            value: &value,
+            primary: String::new(),
+            alternate: String::new(),
+            max_len: self.max_len,
+            slavo_germanic: false,
+        };
+        enc.slavo_germanic =
+            value.contains(&'W') || value.contains(&'K') || enc.find("CZ") || enc.find("WITZ");
+        enc.run();
+        (enc.primary, enc.alternate)
+    }
+}
+
+impl PhoneticEncoder for DoubleMetaphone {
+    fn encode(&self, word: &str) -> Vec<String> {
+        let (primary, alternate) = self.encode_pair(word);
+        let mut codes = Vec::with_capacity(2);
+        if !primary.is_empty() {
+            codes.push(primary.clone());
+        }
+        if !alternate.is_empty() && alternate != primary {
+            codes.push(alternate);
+        }
+        codes
+    }
+}
+
+/// Working state for one Double Metaphone encoding. Positions are `isize` so
+/// look-behind checks before the start of the word simply fail.
+struct Encoder<'a> {
+    value: &'a [char],
+    primary: String,
+    alternate: String,
+    max_len: usize,
+    slavo_germanic: bool,
+}
+
+const L_R_N_M_B_H_F_V_W_SPACE: &[&str] = &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "];
+const ES_EP_EB_EL_EY_IB_IL_IN_IE_EI_ER: &[&str] = &[
+    "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+];
+const L_T_K_S_N_M_B_Z: &[&str] = &["L", "T", "K", "S", "N", "M", "B", "Z"];
+
+impl Encoder<'_> {
+    fn len(&self) -> isize {
+        self.value.len() as isize
+    }
+
+    fn at(&self, i: isize) -> char {
+        if i < 0 || i >= self.len() {
+            '\0'
+        } else {
+            self.value[i as usize]
+        }
+    }
+
+    fn is_vowel(c: char) -> bool {
+        matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    /// True if the `len` characters at `start` equal one of `options`.
+    fn contains(&self, start: isize, len: usize, options: &[&str]) -> bool {
+        if start < 0 || start as usize + len > self.value.len() {
+            return false;
+        }
+        let s = start as usize;
+        let slice = &self.value[s..s + len];
+        options
+            .iter()
+            .any(|o| o.chars().count() == len && o.chars().eq(slice.iter().copied()))
+    }
+
+    fn find(&self, needle: &str) -> bool {
+        let n: Vec<char> = needle.chars().collect();
+        self.value.windows(n.len()).any(|w| w == n.as_slice())
+    }
+
+    fn push(&mut self, primary: &str, alternate: &str) {
+        for (code, part) in [
+            (&mut self.primary, primary),
+            (&mut self.alternate, alternate),
+        ] {
+            for c in part.chars() {
+                if code.len() < self.max_len {
+                    code.push(c);
+                }
+            }
+        }
+    }
+
+    fn both(&mut self, s: &str) {
+        self.push(s, s);
+    }
+
+    fn done(&self) -> bool {
+        self.primary.len() >= self.max_len && self.alternate.len() >= self.max_len
+    }
+
+    fn run(&mut self) {
+        let mut i: isize = 0;
+        if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            i = 1;
+        }
+        while !self.done() && i < self.len() {
+            i = match self.at(i) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if i == 0 {
+                        self.both("A");
+                    }
+                    i + 1
+                }
+                'B' => {
+                    self.both("P");
+                    if self.at(i + 1) == 'B' {
+                        i + 2
+                    } else {
+                        i + 1
+                    }
+                }
+                'Ç' => {
+                    self.both("S");
+                    i + 1
+                }
+                'C' => self.handle_c(i),
+                'D' => self.handle_d(i),
+                'F' => self.simple(i, "F", 'F'),
+                'G' => self.handle_g(i),
+                'H' => self.handle_h(i),
+                'J' => self.handle_j(i),
+                'K' => self.simple(i, "K", 'K'),
+                'L' => self.handle_l(i),
+                'M' => {
+                    self.both("M");
+                    if self.condition_m0(i) {
+                        i + 2
+                    } else {
+                        i + 1
+                    }
+                }
+                'N' => self.simple(i, "N", 'N'),
+                'Ñ' => {
+                    self.both("N");
+                    i + 1
+                }
+                'P' => {
+                    if self.at(i + 1) == 'H' {
+                        self.both("F");
+                        i + 2
+                    } else {
+                        self.both("P");
+                        if self.contains(i + 1, 1, &["P", "B"]) {
+                            i + 2
+                        } else {
+                            i + 1
+                        }
+                    }
+                }
+                'Q' => self.simple(i, "K", 'Q'),
+                'R' => self.handle_r(i),
+                'S' => self.handle_s(i),
+                'T' => self.handle_t(i),
+                'V' => self.simple(i, "F", 'V'),
+                'W' => self.handle_w(i),
+                'X' => self.handle_x(i),
+                'Z' => self.handle_z(i),
+                _ => i + 1,
+            };
+        }
+    }
+
+    /// Emit `code` and skip a doubled `letter`.
+    fn simple(&mut self, i: isize, code: &str, letter: char) -> isize {
+        self.both(code);
+        if self.at(i + 1) == letter {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_c(&mut self, i: isize) -> isize {
+        if self.condition_c0(i) {
+            self.both("K");
+            i + 2
+        } else if i == 0 && self.contains(i, 6, &["CAESAR"]) {
+            self.both("S");
+            i + 2
+        } else if self.contains(i, 2, &["CH"]) {
+            self.handle_ch(i)
+        } else if self.contains(i, 2, &["CZ"]) && !self.contains(i - 2, 4, &["WICZ"]) {
+            self.push("S", "X");
+            i + 2
+        } else if self.contains(i + 1, 3, &["CIA"]) {
+            self.both("X");
+            i + 3
+        } else if self.contains(i, 2, &["CC"]) && !(i == 1 && self.at(0) == 'M') {
+            self.handle_cc(i)
+        } else if self.contains(i, 2, &["CK", "CG", "CQ"]) {
+            self.both("K");
+            i + 2
+        } else if self.contains(i, 2, &["CI", "CE", "CY"]) {
+            if self.contains(i, 3, &["CIO", "CIE", "CIA"]) {
+                self.push("S", "X");
+            } else {
+                self.both("S");
+            }
+            i + 2
+        } else {
+            self.both("K");
+            if self.contains(i + 1, 2, &[" C", " Q", " G"]) {
+                i + 3
+            } else if self.contains(i + 1, 1, &["C", "K", "Q"])
+                && !self.contains(i + 1, 2, &["CE", "CI"])
+            {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn condition_c0(&self, i: isize) -> bool {
+        if self.contains(i, 4, &["CHIA"]) {
+            true
+        } else if i <= 1 || Self::is_vowel(self.at(i - 2)) || !self.contains(i - 1, 3, &["ACH"]) {
+            false
+        } else {
+            let c = self.at(i + 2);
+            (c != 'I' && c != 'E') || self.contains(i - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn handle_cc(&mut self, i: isize) -> isize {
+        if self.contains(i + 2, 1, &["I", "E", "H"]) && !self.contains(i + 2, 2, &["HU"]) {
+            if (i == 1 && self.at(i - 1) == 'A') || self.contains(i - 1, 5, &["UCCEE", "UCCES"]) {
+                self.both("KS");
+            } else {
+                self.both("X");
+            }
+            i + 3
+        } else {
+            self.both("K");
+            i + 2
+        }
+    }
+
+    fn handle_ch(&mut self, i: isize) -> isize {
+        if i > 0 && self.contains(i, 4, &["CHAE"]) {
+            self.push("K", "X");
+        } else if self.condition_ch0(i) || self.condition_ch1(i) {
+            self.both("K");
+        } else if i > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.both("K");
+            } else {
+                self.push("X", "K");
+            }
+        } else {
+            self.both("X");
+        }
+        i + 2
+    }
+
+    fn condition_ch0(&self, i: isize) -> bool {
+        i == 0
+            && (self.contains(i + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(i + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, i: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(i - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(i + 2, 1, &["T", "S"])
+            || ((self.contains(i - 1, 1, &["A", "O", "U", "E"]) || i == 0)
+                && (self.contains(i + 2, 1, L_R_N_M_B_H_F_V_W_SPACE) || i + 1 == self.len() - 1))
+    }
+
+    fn handle_d(&mut self, i: isize) -> isize {
+        if self.contains(i, 2, &["DG"]) {
+            if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+                self.both("J");
+                i + 3
+            } else {
+                self.both("TK");
+                i + 2
+            }
+        } else if self.contains(i, 2, &["DT", "DD"]) {
+            self.both("T");
+            i + 2
+        } else {
+            self.both("T");
+            i + 1
+        }
+    }
+
+    fn handle_g(&mut self, i: isize) -> isize {
+        let next = self.at(i + 1);
+        if next == 'H' {
+            self.handle_gh(i)
+        } else if next == 'N' {
+            if i == 1 && Self::is_vowel(self.at(0)) && !self.slavo_germanic {
+                self.push("KN", "N");
+            } else if !self.contains(i + 2, 2, &["EY"]) && next != 'Y' && !self.slavo_germanic {
+                self.push("N", "KN");
+            } else {
+                self.both("KN");
+            }
+            i + 2
+        } else if self.contains(i + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.push("KL", "L");
+            i + 2
+        } else if (i == 0
+            && (next == 'Y' || self.contains(i + 1, 2, ES_EP_EB_EL_EY_IB_IL_IN_IE_EI_ER)))
+            || ((self.contains(i + 1, 2, &["ER"]) || next == 'Y')
+                && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+                && !self.contains(i - 1, 1, &["E", "I"])
+                && !self.contains(i - 1, 3, &["RGY", "OGY"]))
+        {
+            self.push("K", "J");
+            i + 2
+        } else if self.contains(i + 1, 1, &["E", "I", "Y"])
+            || self.contains(i - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(i + 1, 2, &["ET"])
+            {
+                self.both("K");
+            } else if self.contains(i + 1, 3, &["IER"]) {
+                self.both("J");
+            } else {
+                self.push("J", "K");
+            }
+            i + 2
+        } else if next == 'G' {
+            self.both("K");
+            i + 2
+        } else {
+            self.both("K");
+            i + 1
+        }
+    }
+
+    fn handle_gh(&mut self, i: isize) -> isize {
+        if i > 0 && !Self::is_vowel(self.at(i - 1)) {
+            self.both("K");
+        } else if i == 0 {
+            if self.at(i + 2) == 'I' {
+                self.both("J");
+            } else {
+                self.both("K");
+            }
+        } else if (i > 1 && self.contains(i - 2, 1, &["B", "H", "D"]))
+            || (i > 2 && self.contains(i - 3, 1, &["B", "H", "D"]))
+            || (i > 3 && self.contains(i - 4, 1, &["B", "H"]))
+        {
+            // e.g. "hugh", "bough": silent
+        } else if i > 2
+            && self.at(i - 1) == 'U'
+            && self.contains(i - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // e.g. "laugh", "tough"
+            self.both("F");
+        } else if i > 0 && self.at(i - 1) != 'I' {
+            self.both("K");
+        }
+        i + 2
+    }
+
+    fn handle_h(&mut self, i: isize) -> isize {
+        if (i == 0 || Self::is_vowel(self.at(i - 1))) && Self::is_vowel(self.at(i + 1)) {
+            self.both("H");
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_j(&mut self, i: isize) -> isize {
+        if self.contains(i, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            if (i == 0 && (self.at(i + 4) == ' ' || self.len() == 4))
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.both("H");
+            } else {
+                self.push("J", "H");
+            }
+            return i + 1;
+        }
+        if i == 0 {
+            self.push("J", "A");
+        } else if Self::is_vowel(self.at(i - 1))
+            && !self.slavo_germanic
+            && matches!(self.at(i + 1), 'A' | 'O')
+        {
+            self.push("J", "H");
+        } else if i == self.len() - 1 {
+            self.push("J", "");
+        } else if !self.contains(i + 1, 1, L_T_K_S_N_M_B_Z)
+            && !self.contains(i - 1, 1, &["S", "K", "L"])
+        {
+            self.both("J");
+        }
+        if self.at(i + 1) == 'J' {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_l(&mut self, i: isize) -> isize {
+        if self.at(i + 1) == 'L' {
+            if self.condition_l0(i) {
+                self.push("L", "");
+            } else {
+                self.both("L");
+            }
+            i + 2
+        } else {
+            self.both("L");
+            i + 1
+        }
+    }
+
+    fn condition_l0(&self, i: isize) -> bool {
+        let n = self.len();
+        (i == n - 3 && self.contains(i - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(n - 2, 2, &["AS", "OS"]) || self.contains(n - 1, 1, &["A", "O"]))
+                && self.contains(i - 1, 4, &["ALLE"]))
+    }
+
+    fn condition_m0(&self, i: isize) -> bool {
+        self.at(i + 1) == 'M'
+            || (self.contains(i - 1, 3, &["UMB"])
+                && (i + 1 == self.len() - 1 || self.contains(i + 2, 2, &["ER"])))
+    }
+
+    fn handle_r(&mut self, i: isize) -> isize {
+        if i == self.len() - 1
+            && !self.slavo_germanic
+            && self.contains(i - 2, 2, &["IE"])
+            && !self.contains(i - 4, 2, &["ME", "MA"])
+        {
+            self.push("", "R");
+        } else {
+            self.both("R");
+        }
+        if self.at(i + 1) == 'R' {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_s(&mut self, i: isize) -> isize {
+        if self.contains(i - 1, 3, &["ISL", "YSL"]) {
+            i + 1
+        } else if i == 0 && self.contains(i, 5, &["SUGAR"]) {
+            self.push("X", "S");
+            i + 1
+        } else if self.contains(i, 2, &["SH"]) {
+            if self.contains(i + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.both("S");
+            } else {
+                self.both("X");
+            }
+            i + 2
+        } else if self.contains(i, 3, &["SIO", "SIA"]) || self.contains(i, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.both("S");
+            } else {
+                self.push("S", "X");
+            }
+            i + 3
+        } else if (i == 0 && self.contains(i + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(i + 1, 1, &["Z"])
+        {
+            self.push("S", "X");
+            if self.contains(i + 1, 1, &["Z"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        } else if self.contains(i, 2, &["SC"]) {
+            self.handle_sc(i)
+        } else {
+            if i == self.len() - 1 && self.contains(i - 2, 2, &["AI", "OI"]) {
+                self.push("", "S");
+            } else {
+                self.both("S");
+            }
+            if self.contains(i + 1, 1, &["S", "Z"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, i: isize) -> isize {
+        if self.at(i + 2) == 'H' {
+            if self.contains(i + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(i + 3, 2, &["ER", "EN"]) {
+                    self.push("X", "SK");
+                } else {
+                    self.both("SK");
+                }
+            } else if i == 0 && !Self::is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.push("X", "S");
+            } else {
+                self.both("X");
+            }
+        } else if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+            self.both("S");
+        } else {
+            self.both("SK");
+        }
+        i + 3
+    }
+
+    fn handle_t(&mut self, i: isize) -> isize {
+        if self.contains(i, 4, &["TION"]) || self.contains(i, 3, &["TIA", "TCH"]) {
+            self.both("X");
+            i + 3
+        } else if self.contains(i, 2, &["TH"]) || self.contains(i, 3, &["TTH"]) {
+            if self.contains(i + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                self.both("T");
+            } else {
+                self.push("0", "T");
+            }
+            i + 2
+        } else {
+            self.both("T");
+            if self.contains(i + 1, 1, &["T", "D"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, i: isize) -> isize {
+        if self.contains(i, 2, &["WR"]) {
+            self.both("R");
+            return i + 2;
+        }
+        if i == 0 && (Self::is_vowel(self.at(i + 1)) || self.contains(i, 2, &["WH"])) {
+            if Self::is_vowel(self.at(i + 1)) {
+                self.push("A", "F");
+            } else {
+                self.both("A");
+            }
+            i + 1
+        } else if (i == self.len() - 1 && Self::is_vowel(self.at(i - 1)))
+            || self.contains(i - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            self.push("", "F");
+            i + 1
+        } else if self.contains(i, 4, &["WICZ", "WITZ"]) {
+            self.push("TS", "FX");
+            i + 4
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_x(&mut self, i: isize) -> isize {
+        if i == 0 {
+            self.both("S");
+            return i + 1;
+        }
+        let french_ending = i == self.len() - 1
+            && (self.contains(i - 3, 3, &["IAU", "EAU"]) || self.contains(i - 2, 2, &["AU", "OU"]));
+        if !french_ending {
+            self.both("KS");
+        }
+        if self.contains(i + 1, 1, &["C", "X"]) {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_z(&mut self, i: isize) -> isize {
+        if self.at(i + 1) == 'H' {
+            self.both("J");
+            return i + 2;
+        }
+        if self.contains(i + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && i > 0 && self.at(i - 1) != 'T')
+        {
+            self.push("S", "TS");
+        } else {
+            self.both("S");
+        }
+        if self.at(i + 1) == 'Z' {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(Soundex.encode("Robert"), vec!["R163"]);
+        assert_eq!(Soundex.encode("Rupert"), vec!["R163"]);
+        assert_eq!(Soundex.encode("Ashcraft"), vec!["A261"]);
+        assert_eq!(Soundex.encode("Tymczak"), vec!["T522"]);
+        assert_eq!(Soundex.encode("Pfister"), vec!["P236"]);
+        assert!(Soundex.encode("123").is_empty());
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        let dm = DoubleMetaphone::default();
+        assert_eq!(dm.encode_pair("phonetic").0, "FNTK");
+        assert_eq!(dm.encode_pair("fonetik").0, "FNTK");
+        assert_eq!(dm.encode_pair("Thomas").0, "TMS");
+        assert_eq!(
+            dm.encode_pair("Smith"),
+            ("SM0".to_string(), "XMT".to_string())
+        );
+        assert_eq!(
+            dm.encode_pair("Schmidt"),
+            ("XMT".to_string(), "SMT".to_string())
+        );
+        assert_eq!(dm.encode_pair("knight").0, "NT");
+        assert_eq!(dm.encode_pair("laugh").0, "LF");
+        assert_eq!(dm.encode("Smith"), vec!["SM0", "XMT"]);
+    }
+}
@@ -0,0 +1,296 @@
+/*!
+reload module
+
+`ReloadableSymSpell` keeps a `SymSpell` in sync with a dictionary file. It
+polls the file's modification time and size (no platform file watcher is
+needed), rebuilds the index off to the side when they change and atomically
+swaps it in through a `SharedSymSpell`, so readers never wait for a rebuild.
+
+A reload that fails (missing file, malformed line, ...) leaves the previous
+index in place; the error is kept and reported by `last_error` until the next
+successful reload.
+
+Polling can be driven manually with `reload_if_changed`, or by a background
+thread started with `watch`, which stops when the handle is dropped.
+*/
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use crate::distance::DistanceMetric;
+use crate::loader::LoadOptions;
+use crate::lookup::Lookup;
+use crate::noisy::NoisyChannel;
+use crate::shared::SharedSymSpell;
+use crate::symspell::{Suggestion, SymSpell, Verbosity};
+
+type Builder = dyn Fn(&Path) -> io::Result<SymSpell> + Send + Sync;
+
+/// A `SymSpell` rebuilt from a dictionary file whenever the file changes.
+pub struct ReloadableSymSpell {
+    inner: Arc<Inner>,
+    watcher: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+struct Inner {
+    path: PathBuf,
+    build: Box<Builder>,
+    shared: SharedSymSpell,
+    /// Modification time and length of the file last loaded (or attempted).
+    stamp: Mutex<Option<(SystemTime, u64)>>,
+    last_error: Mutex<Option<Arc<io::Error>>>,
+}
+
+impl ReloadableSymSpell {
+    /// Load `path` with `SymSpell::from_file`, reloading it the same way.
+    pub fn open<P: Into<PathBuf>>(
+        path: P,
+        max_distance: u8,
+        options: LoadOptions,
+    ) -> io::Result<Self> {
+        Self::with_builder(path, move |path| {
+            SymSpell::from_file(max_distance, path, &options)
+        })
+    }
+
+    /// Load `path` with `build`, which is called again on every reload (e.g.
+    /// to configure a metric or edit costs on the fresh index).
+    ///
+    /// Fails if the initial load fails.
+    pub fn with_builder<P, F>(path: P, build: F) -> io::Result<Self>
+    where
+        P: Into<PathBuf>,
+        F: Fn(&Path) -> io::Result<SymSpell> + Send + Sync + 'static,
+    {
+        let path = path.into();
+        let stamp = file_stamp(&path)?;
+        let sym = build(&path)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                path,
+                build: Box::new(build),
+                shared: SharedSymSpell::new(sym),
+                stamp: Mutex::new(Some(stamp)),
+                last_error: Mutex::new(None),
+            }),
+            watcher: None,
+        })
+    }
+
+    /// Poll the file every `interval` on a background thread, reloading it
+    /// when it changes. The thread stops when this handle is dropped.
+    pub fn watch(mut self, interval: Duration) -> Self {
+        self.stop_watching();
+        let (stop, stopped) = mpsc::channel::<()>();
+        let inner = Arc::clone(&self.inner);
+        let handle = thread::spawn(move || {
+            // Dropping the sender disconnects the channel and ends the loop.
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                // Failures are recorded in `last_error`.
+                let _ = inner.reload_if_changed();
+            }
+        });
+        self.watcher = Some((stop, handle));
+        self
+    }
+
+    /// The watched dictionary file.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// The current index.
+    pub fn snapshot(&self) -> Arc<SymSpell> {
+        self.inner.shared.snapshot()
+    }
+
+    /// The shared index readers use. Words added through it are lost on the
+    /// next reload.
+    pub fn shared(&self) -> &SharedSymSpell {
+        &self.inner.shared
+    }
+
+    /// Look up suggestions for `term` in the current index.
+    pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        self.inner.shared.lookup(term, max_distance, verbosity)
+    }
+
+    /// Reload the file if its modification time or size changed since the
+    /// last attempt. Returns whether a new index was swapped in.
+    pub fn reload_if_changed(&self) -> io::Result<bool> {
+        self.inner.reload_if_changed()
+    }
+
+    /// Reload the file unconditionally.
+    pub fn reload(&self) -> io::Result<()> {
+        self.inner.reload()
+    }
+
+    /// The error of the most recent reload, if it failed. Cleared by the next
+    /// successful reload.
+    pub fn last_error(&self) -> Option<Arc<io::Error>> {
+        lock(&self.inner.last_error).clone()
+    }
+
+    fn stop_watching(&mut self) {
+        if let Some((stop, handle)) = self.watcher.take() {
+            drop(stop);
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ReloadableSymSpell {
+    fn drop(&mut self) {
+        self.stop_watching();
+    }
+}
+
+impl Lookup for ReloadableSymSpell {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        ReloadableSymSpell::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.snapshot().max_distance()
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        self.snapshot().frequency(word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.snapshot().word_lengths()
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.snapshot().distance_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.snapshot().noisy_channel()
+    }
+}
+
+impl Inner {
+    fn reload_if_changed(&self) -> io::Result<bool> {
+        // A successful stat says nothing about the file's contents, so only
+        // a failure touches `last_error`.
+        let stamp = file_stamp(&self.path).map_err(|e| self.fail(e))?;
+        {
+            let mut last = lock(&self.stamp);
+            if *last == Some(stamp) {
+                return Ok(false);
+            }
+            // Remember the attempt so a broken file is not re-parsed on every
+            // poll; it is retried once it changes again.
+            *last = Some(stamp);
+        }
+        self.reload().map(|()| true)
+    }
+
+    fn reload(&self) -> io::Result<()> {
+        let sym = (self.build)(&self.path).map_err(|e| self.fail(e))?;
+        self.shared.replace(sym);
+        *lock(&self.last_error) = None;
+        Ok(())
+    }
+
+    /// Store `error` in `last_error`, returning a copy for the caller.
+    fn fail(&self, error: io::Error) -> io::Error {
+        let copy = io::Error::new(error.kind(), error.to_string());
+        *lock(&self.last_error) = Some(Arc::new(error));
+        copy
+    }
+}
+
+fn file_stamp(path: &Path) -> io::Result<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path)?;
+    Ok((meta.modified()?, meta.len()))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// A fresh dictionary file path under the system temp directory.
+    fn temp_dictionary(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "symspellrs-{}-{}-{}.txt",
+            name,
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_reload_if_changed() {
+        let path = temp_dictionary("reload", "hello 5\n");
+        let opts = LoadOptions::new().with_freq(true);
+        let dict = ReloadableSymSpell::open(&path, 2, opts).unwrap();
+        assert!(!dict.reload_if_changed().unwrap());
+        assert_eq!(dict.lookup("helo", 2, Verbosity::Top)[0].term, "hello");
+
+        std::fs::write(&path, "hello 5\nhelot 50\n").unwrap();
+        assert!(dict.reload_if_changed().unwrap());
+        assert_eq!(dict.lookup("helo", 2, Verbosity::Top)[0].term, "helot");
+
+        // A broken file keeps the previous index and reports the error.
+        std::fs::write(&path, "hello 5\nhelp many\n").unwrap();
+        let err = dict.reload_if_changed().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(dict.last_error().is_some());
+        assert_eq!(dict.snapshot().frequency("helot"), Some(50));
+        // Unchanged since the failed attempt: not retried, and the error is
+        // still reported.
+        assert!(!dict.reload_if_changed().unwrap());
+        assert!(!dict.reload_if_changed().unwrap());
+        assert_eq!(
+            dict.last_error().map(|e| e.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        std::fs::write(&path, "help 1\n").unwrap();
+        assert!(dict.reload_if_changed().unwrap());
+        assert!(dict.last_error().is_none());
+        assert_eq!(dict.snapshot().frequency("helot"), None);
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            dict.reload_if_changed().unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(dict.snapshot().frequency("help"), Some(1));
+    }
+
+    #[test]
+    fn test_watch_reloads_in_background() {
+        let path = temp_dictionary("watch", "apple\n");
+        let dict = ReloadableSymSpell::open(&path, 2, LoadOptions::new())
+            .unwrap()
+            .watch(Duration::from_millis(5));
+
+        std::fs::write(&path, "apple\nbanana\n").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while dict.snapshot().frequency("banana").is_none() {
+            assert!(Instant::now() < deadline, "dictionary was not reloaded");
+            thread::sleep(Duration::from_millis(5));
+        }
+        drop(dict);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
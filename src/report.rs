@@ -0,0 +1,303 @@
+/*!
+report module
+
+Machine-readable output for `check_text` results, for code review tools and
+dashboards:
+
+- JSON Lines (`write_json_lines`): one object per misspelling with its path,
+  position (1-based line/column, byte and character offsets), token and
+  suggestions (`term`, `distance`, `frequency`);
+- SARIF 2.1.0 (`SarifReport`): one run with a `spelling` rule, a result per
+  misspelling and a fix per suggestion. Columns are Unicode code points, as
+  in `Misspelling::column`.
+
+JSON is written by hand to keep the crate free of serialization dependencies.
+*/
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::text::Misspelling;
+
+/// SARIF rule id used for every result.
+pub const SARIF_RULE_ID: &str = "spelling";
+
+/// `misspelling` found in `path` as a single-line JSON object.
+pub fn misspelling_json(path: &str, misspelling: &Misspelling) -> String {
+    let m = misspelling;
+    let mut out = format!(
+        "{{\"path\":{},\"line\":{},\"column\":{},\"byte_start\":{},\"byte_end\":{},\
+         \"char_start\":{},\"char_end\":{},\"token\":{},\"suggestions\":[",
+        json_string(path),
+        m.line,
+        m.column,
+        m.byte_range.start,
+        m.byte_range.end,
+        m.char_range.start,
+        m.char_range.end,
+        json_string(&m.token)
+    );
+    for (i, s) in m.suggestions.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            "{{\"term\":{},\"distance\":{},\"frequency\":{}}}",
+            json_string(&s.term),
+            s.distance,
+            s.frequency
+        );
+    }
+    out.push_str("]}");
+    out
+}
+
+/// Write each misspelling of `path` as one JSON object per line.
+pub fn write_json_lines<W: Write>(
+    out: &mut W,
+    path: &str,
+    misspellings: &[Misspelling],
+) -> io::Result<()> {
+    for m in misspellings {
+        writeln!(out, "{}", misspelling_json(path, m))?;
+    }
+    Ok(())
+}
+
+/// Collects misspellings from any number of files into one SARIF log.
+#[derive(Debug, Clone)]
+pub struct SarifReport {
+    tool_name: String,
+    tool_version: String,
+    results: Vec<(String, Misspelling)>,
+}
+
+impl Default for SarifReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SarifReport {
+    /// An empty report attributed to `symspellrs` with this crate's version.
+    pub fn new() -> Self {
+        Self {
+            tool_name: "symspellrs".to_string(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Attribute the report to another tool (e.g. a front end's own name).
+    pub fn with_tool(mut self, name: &str, version: &str) -> Self {
+        self.tool_name = name.to_string();
+        self.tool_version = version.to_string();
+        self
+    }
+
+    /// Add the misspellings found in `path`.
+    pub fn add(&mut self, path: &str, misspellings: &[Misspelling]) {
+        self.results
+            .extend(misspellings.iter().map(|m| (path.to_string(), m.clone())));
+    }
+
+    /// Number of results added so far.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if no result was added.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The SARIF log as a JSON document.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        out.push_str(
+            "{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+             \"version\":\"2.1.0\",\"runs\":[{\"tool\":{\"driver\":{",
+        );
+        let _ = write!(
+            out,
+            "\"name\":{},\"version\":{},\"rules\":[{{\"id\":\"{}\",\
+             \"shortDescription\":{{\"text\":\"Misspelled word\"}}}}]}}}},\
+             \"columnKind\":\"unicodeCodePoints\",\"results\":[",
+            json_string(&self.tool_name),
+            json_string(&self.tool_version),
+            SARIF_RULE_ID
+        );
+        for (i, (path, m)) in self.results.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            sarif_result(&mut out, path, m);
+        }
+        out.push_str("]}]}");
+        out
+    }
+
+    /// Write the SARIF log followed by a newline.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "{}", self.to_json())
+    }
+}
+
+fn sarif_result(out: &mut String, path: &str, m: &Misspelling) {
+    let uri = json_string(&path_uri(path));
+    let region = format!(
+        "{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{}}}",
+        m.line,
+        m.column,
+        m.line,
+        m.column + m.char_range.len()
+    );
+    let mut message = format!("Unknown word '{}'.", m.token);
+    if !m.suggestions.is_empty() {
+        let terms: Vec<&str> = m.suggestions.iter().map(|s| s.term.as_str()).collect();
+        let _ = write!(message, " Suggestions: {}.", terms.join(", "));
+    }
+    let _ = write!(
+        out,
+        "{{\"ruleId\":\"{}\",\"level\":\"warning\",\"message\":{{\"text\":{}}},\
+         \"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\
+         \"region\":{}}}}}],\"fixes\":[",
+        SARIF_RULE_ID,
+        json_string(&message),
+        uri,
+        region
+    );
+    for (i, s) in m.suggestions.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            "{{\"description\":{{\"text\":{}}},\"artifactChanges\":[{{\"artifactLocation\":\
+             {{\"uri\":{}}},\"replacements\":[{{\"deletedRegion\":{},\
+             \"insertedContent\":{{\"text\":{}}}}}]}}]}}",
+            json_string(&format!("Replace with '{}'", s.term)),
+            uri,
+            region,
+            json_string(&s.term)
+        );
+    }
+    out.push_str("]}");
+}
+
+/// `s` as a quoted JSON string.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A URI reference for a file path. `\` becomes `/`; absolute paths (`/src`,
+/// `C:\src`) become `file:` URIs and relative ones stay relative. Characters
+/// outside the unreserved set (plus `/`) are percent-encoded, including any
+/// `:` that is not a drive letter's, so no path reads as a URI scheme.
+fn path_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut out = String::with_capacity(path.len() + 8);
+    let mut rest = path.as_str();
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":/" {
+        out.push_str("file:///");
+        out.push_str(&path[..2]);
+        rest = &path[2..];
+    } else if path.starts_with('/') {
+        out.push_str("file://");
+    }
+    for b in rest.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{:02X}", b);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::SymSpell;
+
+    fn misspellings(text: &str) -> Vec<Misspelling> {
+        let sym = SymSpell::from_iter(2, [("world", 10usize), ("word", 3)]);
+        sym.check_text(text).collect()
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let mut out = Vec::new();
+        write_json_lines(&mut out, "dir/a \"b\".txt", &misspellings("é worl")).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "{\"path\":\"dir/a \\\"b\\\".txt\",\"line\":1,\"column\":1,\"byte_start\":0,\
+             \"byte_end\":2,\"char_start\":0,\"char_end\":1,\"token\":\"é\",\"suggestions\":[]}"
+        );
+        assert_eq!(
+            lines[1],
+            "{\"path\":\"dir/a \\\"b\\\".txt\",\"line\":1,\"column\":3,\"byte_start\":3,\
+             \"byte_end\":7,\"char_start\":2,\"char_end\":6,\"token\":\"worl\",\"suggestions\":\
+             [{\"term\":\"world\",\"distance\":1,\"frequency\":10},\
+             {\"term\":\"word\",\"distance\":1,\"frequency\":3}]}"
+        );
+    }
+
+    #[test]
+    fn test_sarif() {
+        let mut report = SarifReport::new().with_tool("spellcheck", "1.0");
+        assert!(report.is_empty());
+        report.add("src/my file.rs", &misspellings("a\n worl"));
+        assert_eq!(report.len(), 2);
+        let json = report.to_json();
+        assert!(json.starts_with("{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\""));
+        assert!(json.contains("\"name\":\"spellcheck\",\"version\":\"1.0\""));
+        assert!(json.contains("\"uri\":\"src/my%20file.rs\""));
+        assert!(json.contains(
+            "\"region\":{\"startLine\":2,\"startColumn\":2,\"endLine\":2,\"endColumn\":6}"
+        ));
+        assert!(json.contains("Unknown word 'worl'. Suggestions: world, word."));
+        assert!(json.contains("\"insertedContent\":{\"text\":\"world\"}"));
+        assert_eq!(json.matches("\"ruleId\"").count(), 2);
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+
+    #[test]
+    fn test_path_uri() {
+        assert_eq!(path_uri("src\\my file.rs"), "src/my%20file.rs");
+        assert_eq!(path_uri("C:\\src\\x.rs"), "file:///C:/src/x.rs");
+        assert_eq!(path_uri("/home/me/a#b?.rs"), "file:///home/me/a%23b%3F.rs");
+        // A colon elsewhere must not read as a scheme.
+        assert_eq!(path_uri("c:x.rs"), "c%3Ax.rs");
+        assert_eq!(path_uri("é.rs"), "%C3%A9.rs");
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(
+            json_string("a\"b\\c\nd\u{1}é"),
+            "\"a\\\"b\\\\c\\nd\\u0001é\""
+        );
+    }
+}
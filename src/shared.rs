@@ -0,0 +1,186 @@
+/*!
+shared module
+
+`SharedSymSpell` lets many threads look words up while others add or remove
+words, e.g. to learn words from users at runtime.
+
+Readers work on an immutable snapshot (`Arc<SymSpell>`): taking one only holds
+a read lock long enough to clone the `Arc`, and the lookup itself runs without
+any lock. Writers apply a whole batch of changes to a private copy of the
+current index and then publish it in one step, so a lookup sees either all or
+none of a batch and never a half-updated `deletes` index.
+
+Every batch copies the index, so writes should be grouped rather than issued
+one word at a time.
+*/
+
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use crate::distance::DistanceMetric;
+use crate::lookup::Lookup;
+use crate::noisy::NoisyChannel;
+use crate::symspell::{Suggestion, SymSpell, Verbosity};
+
+/// A `SymSpell` that can be updated while it is being read from many threads.
+pub struct SharedSymSpell {
+    current: RwLock<Arc<SymSpell>>,
+    /// Serialises writers so no batch is lost to a concurrent one.
+    writer: Mutex<()>,
+}
+
+impl SharedSymSpell {
+    /// Share `sym` between threads.
+    pub fn new(sym: SymSpell) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(sym)),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// The current snapshot. It is unaffected by later updates, so several
+    /// lookups on the same snapshot see a consistent dictionary.
+    pub fn snapshot(&self) -> Arc<SymSpell> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Look up suggestions for `term` in the current snapshot.
+    pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        self.snapshot().lookup(term, max_distance, verbosity)
+    }
+
+    /// Apply a batch of changes with `f` on a copy of the current index, then
+    /// publish the result atomically. Returns whatever `f` returns.
+    ///
+    /// Lookups keep using the previous snapshot until `f` has finished. If `f`
+    /// panics, nothing is published.
+    pub fn update<R>(&self, f: impl FnOnce(&mut SymSpell) -> R) -> R {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut next = SymSpell::clone(&self.snapshot());
+        let result = f(&mut next);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(next);
+        result
+    }
+
+    /// Publish `sym` in place of the current index, returning the previous
+    /// snapshot.
+    pub fn replace(&self, sym: SymSpell) -> Arc<SymSpell> {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *current, Arc::new(sym))
+    }
+
+    /// Add or update `(word, frequency)` entries in one batch.
+    pub fn add_words<I, S>(&self, words: I)
+    where
+        I: IntoIterator<Item = (S, usize)>,
+        S: Into<String>,
+    {
+        self.update(|sym| sym.load_iter(words));
+    }
+
+    /// Remove `words` in one batch, returning how many were present.
+    pub fn remove_words<I, S>(&self, words: I) -> usize
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.update(|sym| {
+            words
+                .into_iter()
+                .filter(|w| sym.remove(w.as_ref()).is_some())
+                .count()
+        })
+    }
+}
+
+impl From<SymSpell> for SharedSymSpell {
+    fn from(sym: SymSpell) -> Self {
+        Self::new(sym)
+    }
+}
+
+impl Lookup for SharedSymSpell {
+    fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        SharedSymSpell::lookup(self, term, max_distance, verbosity)
+    }
+
+    fn max_distance(&self) -> u8 {
+        self.snapshot().max_distance()
+    }
+
+    fn frequency(&self, word: &str) -> Option<usize> {
+        self.snapshot().frequency(word)
+    }
+
+    fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.snapshot().word_lengths()
+    }
+
+    fn distance_metric(&self) -> Arc<dyn DistanceMetric> {
+        self.snapshot().distance_metric()
+    }
+
+    fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.snapshot().noisy_channel()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_snapshot_isolation() {
+        let shared = SharedSymSpell::new(SymSpell::from_iter(2, [("hello", 10usize)]));
+        let before = shared.snapshot();
+
+        shared.add_words([("help", 5usize), ("world", 20)]);
+        assert_eq!(shared.remove_words(["hello", "missing"]), 1);
+
+        // The old snapshot still sees the old dictionary.
+        assert_eq!(before.frequency("hello"), Some(10));
+        assert_eq!(before.frequency("world"), None);
+
+        assert_eq!(shared.lookup("wrld", 2, Verbosity::Top)[0].term, "world");
+        assert_eq!(shared.lookup("helo", 2, Verbosity::Top)[0].term, "help");
+        assert!(!Lookup::contains(&shared, "hello"));
+    }
+
+    #[test]
+    fn test_shared_concurrent_updates() {
+        let shared = SharedSymSpell::new(SymSpell::new(2));
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for i in 0..50 {
+                    // Each batch adds a word and its plural together.
+                    let word = format!("word{i}x");
+                    shared.add_words([(word.clone(), 1usize), (format!("{word}s"), 1)]);
+                }
+            });
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..200 {
+                        let snapshot = shared.snapshot();
+                        // A batch is either fully visible or not at all.
+                        for i in 0..50 {
+                            let word = format!("word{i}x");
+                            let plural = format!("{word}s");
+                            assert_eq!(
+                                snapshot.frequency(&word).is_some(),
+                                snapshot.frequency(&plural).is_some()
+                            );
+                            if snapshot.frequency(&word).is_some() {
+                                let top = snapshot.lookup(&plural, 2, Verbosity::Top);
+                                assert_eq!(top[0].term, plural);
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(shared.snapshot().len(), 100);
+    }
+}
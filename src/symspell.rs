@@ -7,7 +7,15 @@ crate's public API. It implements:
 - `Suggestion` struct for suggestion results
 - `SymSpell` struct which stores a dictionary and a deletion index
 - `generate_deletes` to produce deletion variants for SymSpell indexing
-- `damerau_levenshtein` to compute edit distances with transposition
+
+Candidates are verified with a pluggable `DistanceMetric` (see the `distance`
+module); optimal string alignment is used unless another metric is selected.
+Optional `EditCosts` rank candidates that share the same distance, e.g. to
+prefer substitutions of adjacent keyboard keys, and may add multi-character
+confusion pairs (e.g. OCR errors) that count as single edits. A `Blocklist`
+keeps words out of the suggestions, and a `CorrectionTable` of known
+misspellings is consulted before the deletion index. `NoisyChannel` scoring
+ranks by error-model and frequency probability instead of distance first.
 
 How to populate a SymSpell dictionary
 - Compile-time: use the `include_dictionary!` proc-macro (provided by the
@@ -22,19 +30,79 @@ the expansion) or runtime construction using `from_iter`.
 */
 
 use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blocklist::Blocklist;
+use crate::compound::Segmentation;
+use crate::corrections::CorrectionTable;
+use crate::distance::{DistanceMetric, EditCosts, OptimalStringAlignment};
+use crate::identifier::IdentifierCheck;
+use crate::lookup::Lookup;
+use crate::noisy::NoisyChannel;
+use crate::phonetic::PhoneticEncoder;
+use crate::text::CheckText;
 
 // Compile-time embedding is now provided by the `include_dictionary!` proc-macro
 // (in the `symspellrs_macros` crate) which emits a `phf::Map` in the macro expansion.
 // The prior `embedded_dictionary` build-script approach has been removed.
 
 /// A candidate suggestion returned by `lookup`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Suggestion {
     pub term: String,
     /// Frequency of the candidate in the underlying dictionary.
     pub frequency: usize,
     /// Edit distance from the queried term to the candidate.
     pub distance: u8,
+    /// How the candidate was found.
+    pub origin: Origin,
+    /// Name of the dictionary the candidate came from, set by
+    /// `MultiDictionary`; `None` when querying a single dictionary.
+    pub dictionary: Option<Arc<str>>,
+    /// Log10 noisy-channel probability of the candidate, set when ranking
+    /// with `NoisyChannel` scoring; `None` otherwise.
+    pub score: Option<f64>,
+}
+
+/// How a `Suggestion` was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// Found through the deletion index and verified by edit distance.
+    EditDistance,
+    /// Found only through the phonetic index; `distance` may exceed `max_distance`.
+    Phonetic,
+    /// Found through both the deletion index and the phonetic index.
+    EditDistanceAndPhonetic,
+    /// Found by `EmbeddedSymSpell`'s dictionary scan after the deletion index
+    /// yielded nothing (see `Fallback`).
+    FallbackScan,
+    /// Taken from the `CorrectionTable`; `distance` may exceed `max_distance`.
+    CorrectionTable,
+}
+
+/// What `EmbeddedSymSpell::lookup` does when the deletion index yields no
+/// suggestion, e.g. because a custom `DistanceMetric` accepts candidates that
+/// share no deletion with the query.
+///
+/// Suggestions found by the scan have `Origin::FallbackScan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Return no suggestions.
+    Disabled,
+    /// Verify every dictionary word of a plausible length.
+    #[default]
+    Full,
+    /// Like `Full`, but stop after examining `max_words` entries or once
+    /// `max_time` has elapsed, whichever comes first.
+    Bounded {
+        max_words: usize,
+        max_time: Option<Duration>,
+    },
+    /// Verify only words whose length is within `max_distance` of the query,
+    /// read from the length buckets emitted by `include_dictionary!` (see
+    /// `EmbeddedSymSpell::with_length_buckets`). Without buckets this is `Full`.
+    LengthBucketed,
 }
 
 /// Controls which suggestions are returned by lookup functions.
@@ -42,7 +110,7 @@ pub struct Suggestion {
 /// - `Top`: return a single best suggestion (closest distance, then highest frequency)
 /// - `Closest`: return all suggestions with the minimal edit distance (sorted by frequency)
 /// - `All`: return all suggestions within max_distance (sorted by distance then frequency)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Verbosity {
     Top,
     Closest,
@@ -59,10 +127,14 @@ pub enum Verbosity {
 /// from generated deletions (strings with one or more characters removed) to
 /// the possible original words. At lookup time, the algorithm enumerates deletions
 /// of the misspelled term and finds candidate words quickly.
+#[derive(Clone)]
 pub struct SymSpell {
     max_distance: u8,
     dictionary: HashMap<String, usize>,
     deletes: HashMap<String, HashSet<String>>,
+    scoring: Scoring,
+    phonetic: Option<PhoneticIndex>,
+    word_lengths: Option<(usize, usize)>,
 }
 
 impl SymSpell {
@@ -72,7 +144,119 @@ impl SymSpell {
             max_distance,
             dictionary: HashMap::new(),
             deletes: HashMap::new(),
+            scoring: Scoring::default(),
+            phonetic: None,
+            word_lengths: None,
+        }
+    }
+
+    /// Use `metric` to verify and rank candidates instead of the default
+    /// optimal string alignment distance.
+    pub fn with_metric<M: DistanceMetric + 'static>(mut self, metric: M) -> Self {
+        self.scoring.metric = Arc::new(metric);
+        self
+    }
+
+    /// Replace the distance metric used by `lookup`.
+    pub fn set_metric<M: DistanceMetric + 'static>(&mut self, metric: M) {
+        self.scoring.metric = Arc::new(metric);
+    }
+
+    /// Use the already shared `metric`, e.g. another dictionary's.
+    pub(crate) fn with_shared_metric(mut self, metric: Arc<dyn DistanceMetric>) -> Self {
+        self.scoring.metric = metric;
+        self
+    }
+
+    /// The distance metric used by `lookup`.
+    pub fn metric(&self) -> &dyn DistanceMetric {
+        &*self.scoring.metric
+    }
+
+    /// The distance metric, shared rather than borrowed.
+    pub(crate) fn shared_metric(&self) -> Arc<dyn DistanceMetric> {
+        Arc::clone(&self.scoring.metric)
+    }
+
+    /// Rank candidates with the same `distance` by their weighted edit cost
+    /// (lowest first) before falling back to frequency.
+    pub fn with_edit_costs(mut self, costs: EditCosts) -> Self {
+        self.scoring.edit_costs = Some(Arc::new(costs));
+        self
+    }
+
+    /// Replace (or clear, with `None`) the weighted edit costs used for ranking.
+    pub fn set_edit_costs(&mut self, costs: Option<EditCosts>) {
+        self.scoring.edit_costs = costs.map(Arc::new);
+    }
+
+    /// The weighted edit costs used for ranking, if any.
+    pub fn edit_costs(&self) -> Option<&EditCosts> {
+        self.scoring.edit_costs.as_deref()
+    }
+
+    /// Never suggest the words of `blocklist` (see the `blocklist` module).
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.set_blocklist(Some(blocklist));
+        self
+    }
+
+    /// Replace (or clear, with `None`) the blocklist.
+    pub fn set_blocklist(&mut self, blocklist: Option<Blocklist>) {
+        self.scoring.blocklist = blocklist.map(Arc::new);
+    }
+
+    /// The blocklist, if any.
+    pub fn blocklist(&self) -> Option<&Blocklist> {
+        self.scoring.blocklist.as_deref()
+    }
+
+    /// Consult `corrections` before the deletion index (see the
+    /// `corrections` module).
+    pub fn with_corrections(mut self, corrections: CorrectionTable) -> Self {
+        self.set_corrections(Some(corrections));
+        self
+    }
+
+    /// Replace (or clear, with `None`) the correction table.
+    pub fn set_corrections(&mut self, corrections: Option<CorrectionTable>) {
+        self.scoring.corrections = corrections.map(Arc::new);
+    }
+
+    /// The correction table, if any.
+    pub fn corrections(&self) -> Option<&CorrectionTable> {
+        self.scoring.corrections.as_deref()
+    }
+
+    /// Rank suggestions by noisy-channel probability instead of distance
+    /// first (see the `noisy` module).
+    pub fn with_noisy_channel(mut self, channel: NoisyChannel) -> Self {
+        self.set_noisy_channel(Some(channel));
+        self
+    }
+
+    /// Replace (or clear, with `None`) the noisy-channel scoring.
+    pub fn set_noisy_channel(&mut self, channel: Option<NoisyChannel>) {
+        self.scoring.noisy_channel = channel;
+    }
+
+    /// The noisy-channel scoring, if enabled.
+    pub fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.scoring.noisy_channel
+    }
+
+    /// Maintain a phonetic index (code -> words) built with `encoder`, used by
+    /// `lookup_phonetic`. Words already loaded are indexed immediately.
+    pub fn with_phonetic<E: PhoneticEncoder + 'static>(mut self, encoder: E) -> Self {
+        let mut index = PhoneticIndex {
+            encoder: Arc::new(encoder),
+            codes: HashMap::new(),
+        };
+        for word in self.dictionary.keys() {
+            index.insert(word);
         }
+        self.phonetic = Some(index);
+        self
     }
 
     /// Build a `SymSpell` instance from an iterator of `(word, frequency)`.
@@ -101,14 +285,69 @@ impl SymSpell {
             }
             // Insert/replace dictionary frequency
             self.dictionary.insert(word.clone(), freq);
+            let len = word.chars().count();
+            self.word_lengths = Some(match self.word_lengths {
+                Some((min, max)) => (min.min(len), max.max(len)),
+                None => (len, len),
+            });
             // Generate deletes and update delete-index
             let dels = generate_deletes(&word, self.max_distance);
             for d in dels {
                 self.deletes.entry(d).or_default().insert(word.clone());
             }
+            if let Some(index) = &mut self.phonetic {
+                index.insert(&word);
+            }
         }
     }
 
+    /// Remove `word` from the dictionary and the deletion (and phonetic)
+    /// index, returning its frequency if it was present.
+    pub fn remove(&mut self, word: &str) -> Option<usize> {
+        let freq = self.dictionary.remove(word)?;
+        for d in generate_deletes(word, self.max_distance) {
+            if let Some(set) = self.deletes.get_mut(&d) {
+                set.remove(word);
+                if set.is_empty() {
+                    self.deletes.remove(&d);
+                }
+            }
+        }
+        if let Some(index) = &mut self.phonetic {
+            index.remove(word);
+        }
+        // Only removing the shortest or longest word can change the bounds.
+        let len = word.chars().count();
+        if self
+            .word_lengths
+            .is_some_and(|(min, max)| len == min || len == max)
+        {
+            self.word_lengths = self.dictionary.keys().map(|w| w.chars().count()).fold(
+                None,
+                |acc, len| match acc {
+                    Some((min, max)) => Some((len.min(min), len.max(max))),
+                    None => Some((len, len)),
+                },
+            );
+        }
+        Some(freq)
+    }
+
+    /// Number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    /// Returns true if the dictionary holds no words.
+    pub fn is_empty(&self) -> bool {
+        self.dictionary.is_empty()
+    }
+
+    /// Iterate over `(word, frequency)` entries, in no particular order.
+    pub fn words(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.dictionary.iter().map(|(w, f)| (w.as_str(), *f))
+    }
+
     /// Look up suggestions for `term`.
     ///
     /// `max_distance` is capped by the instance `max_distance`.
@@ -116,127 +355,178 @@ impl SymSpell {
     /// - `Verbosity::Top` returns a single best suggestion (closest distance, then highest frequency).
     /// - `Verbosity::Closest` returns all suggestions with the minimal edit distance (sorted by frequency desc).
     /// - `Verbosity::All` returns all suggestions with distance <= max_distance, sorted by distance asc then frequency desc.
+    ///
+    /// When `EditCosts` are configured, suggestions with equal distance are
+    /// ordered by weighted edit cost before frequency. With `NoisyChannel`
+    /// scoring they are ordered by `score` instead (see `with_noisy_channel`).
+    ///
+    /// Queries whose length is out of reach of every dictionary word return
+    /// immediately, and `Top` / `Closest` stop searching once no closer
+    /// candidate can exist (see `word_lengths`).
+    ///
+    /// A `CorrectionTable` hit comes first (see `with_corrections`).
     pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
         if term.is_empty() {
             return Vec::new();
         }
-        let max_distance = std::cmp::min(max_distance, self.max_distance);
-
-        // Candidate words found from the deletion index
-        let mut candidates: HashSet<String> = HashSet::new();
-        // Track terms we've already verified
-        let mut considered: HashSet<String> = HashSet::new();
-
-        // SymSpell approach: generate deletions from the query term and find mapped words.
-        let mut queue: Vec<String> = vec![term.to_string()];
-        // To avoid unbounded growth we cap the queue size heuristically:
-        // (this keeps queries reasonable; users may tune logic as needed)
-        let queue_limit = 10000usize;
-
-        for idx in 0..queue.len() {
-            if idx >= queue_limit {
-                break;
-            }
-            // Clone the current element so we don't hold an immutable borrow of `queue`
-            // while also mutating it (e.g. `queue.push(...)`). This resolves the borrow
-            // checker error by avoiding simultaneous mutable and immutable borrows.
-            let current = queue[idx].clone();
-
-            if let Some(set) = self.deletes.get(&current) {
-                for w in set {
-                    candidates.insert(w.clone());
-                }
-            }
-
-            // If we can go deeper generate further deletions
-            // We generate 1-deletions of `current` and push into queue if not already queued.
-            if (current.len() > 1) && (max_distance as usize) > 0 {
-                for i in 0..current.len() {
-                    let mut s = current.clone();
-                    s.remove(i);
-                    if !queue.contains(&s) {
-                        queue.push(s);
-                    }
-                }
-            }
+        let corrected = self.scoring.correction(self, term);
+        if corrected.is_some() && verbosity != Verbosity::All {
+            return corrected.into_iter().collect();
         }
+        prepend_correction(corrected, self.lookup_edits(term, max_distance, verbosity))
+    }
 
-        // Collect results with computed distances
-        let mut results: Vec<Suggestion> = Vec::new();
+    /// Deletion-index suggestions for `term`, without the correction table.
+    fn lookup_edits(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        let max_distance = std::cmp::min(max_distance, self.max_distance);
+        let results = search(
+            self,
+            &self.scoring,
+            term,
+            max_distance,
+            verbosity,
+            self.word_lengths,
+        );
+        self.scoring.select(results, verbosity)
+    }
 
-        // If the exact term exists in the dictionary, include it among candidates (distance 0)
-        if let Some(&freq) = self.dictionary.get(term) {
-            results.push(Suggestion {
-                term: term.to_string(),
-                frequency: freq,
-                distance: 0,
-            });
-            // For Top/Closest, an exact match is already optimal; but we'll still run the general selection below.
+    /// Look up suggestions for `term`, merging edit-distance candidates with
+    /// words that sound alike according to the phonetic index.
+    ///
+    /// Each suggestion's `origin` records how it was found. Phonetic-only
+    /// matches may lie beyond `max_distance` (their `distance` is still the
+    /// true metric distance) and are ranked after all edit-distance matches:
+    /// - `Verbosity::Top` returns the best edit-distance match, or the best
+    ///   phonetic match if there is none.
+    /// - `Verbosity::Closest` returns the closest edit-distance matches, or the
+    ///   closest phonetic matches if there are none.
+    /// - `Verbosity::All` returns edit-distance matches followed by phonetic ones.
+    ///
+    /// As in `lookup`, a `CorrectionTable` hit comes first, and is the only
+    /// suggestion for `Top` and `Closest`. Without a phonetic index (see
+    /// `with_phonetic`) this is `lookup`.
+    pub fn lookup_phonetic(
+        &self,
+        term: &str,
+        max_distance: u8,
+        verbosity: Verbosity,
+    ) -> Vec<Suggestion> {
+        let Some(index) = &self.phonetic else {
+            return self.lookup(term, max_distance, verbosity);
+        };
+        if term.is_empty() {
+            return Vec::new();
+        }
+        let corrected = self.scoring.correction(self, term);
+        if corrected.is_some() && verbosity != Verbosity::All {
+            return corrected.into_iter().collect();
         }
 
-        for cand in candidates {
-            if considered.contains(&cand) {
-                continue;
-            }
-            considered.insert(cand.clone());
-            let distance = damerau_levenshtein(term, &cand);
-            if distance <= max_distance {
-                let freq = *self.dictionary.get(&cand).unwrap_or(&0);
-                results.push(Suggestion {
-                    term: cand.clone(),
-                    frequency: freq,
-                    distance,
-                });
+        let mut edit = self.lookup_edits(term, max_distance, Verbosity::All);
+        let mut sounds_like = index.matches(term);
+        for s in edit.iter_mut() {
+            if sounds_like.remove(s.term.as_str()) {
+                s.origin = Origin::EditDistanceAndPhonetic;
             }
         }
-
-        if results.is_empty() {
-            return Vec::new();
+        let phonetic: Vec<Ranked> = sounds_like
+            .into_iter()
+            .filter(|word| !self.scoring.blocks(word))
+            .map(|word| {
+                let freq = self.dictionary.get(word).copied().unwrap_or(0);
+                let mut ranked = self.scoring.score(term, word, freq);
+                ranked.suggestion.origin = Origin::Phonetic;
+                ranked
+            })
+            .collect();
+        let phonetic = self.scoring.select(phonetic, Verbosity::All);
+
+        if verbosity == Verbosity::All {
+            edit.extend(phonetic);
+            return prepend_correction(corrected, edit);
         }
-
-        // Determine minimal distance among results
-        let min_distance = results.iter().map(|r| r.distance).min().unwrap_or(u8::MAX);
-
+        let primary = if edit.is_empty() { phonetic } else { edit };
+        let mut primary = primary;
+        let min_distance = primary.iter().map(|s| s.distance).min();
         match verbosity {
-            Verbosity::Top => {
-                // Choose suggestions with minimal distance, then pick the one with highest frequency.
-                let mut best: Option<Suggestion> = None;
-                for r in results.into_iter().filter(|r| r.distance == min_distance) {
-                    match &best {
-                        None => best = Some(r),
-                        Some(b) => {
-                            if r.frequency > b.frequency {
-                                best = Some(r);
-                            }
-                        }
-                    }
-                }
-                best.into_iter().collect()
-            }
-            Verbosity::Closest => {
-                // Return all with minimal distance, sorted by frequency desc
-                let mut filtered: Vec<Suggestion> = results
-                    .into_iter()
-                    .filter(|r| r.distance == min_distance)
-                    .collect();
-                filtered.sort_by(|a, b| b.frequency.cmp(&a.frequency));
-                filtered
-            }
-            Verbosity::All => {
-                // Return all within max_distance sorted by distance asc then frequency desc
-                results.sort_by(|a, b| {
-                    a.distance
-                        .cmp(&b.distance)
-                        .then_with(|| b.frequency.cmp(&a.frequency))
-                });
-                results
-            }
+            Verbosity::Top => primary.truncate(1),
+            // Noisy-channel scoring may put a farther suggestion first.
+            _ => primary.retain(|s| Some(s.distance) == min_distance),
         }
+        primary
     }
 
-    /// Small helper to query raw frequency
+    /// Small helper to query raw frequency. Blocked words that are not
+    /// `recognized` have none.
     pub fn frequency(&self, word: &str) -> Option<usize> {
-        self.dictionary.get(word).copied()
+        self.dictionary
+            .get(word)
+            .copied()
+            .filter(|_| !self.scoring.rejects(word))
+    }
+
+    /// Maximum edit distance the index was built for.
+    pub fn max_distance(&self) -> u8 {
+        self.max_distance
+    }
+
+    /// Look up every term of `terms`, returning one suggestion list per term
+    /// in the same order. Repeated terms are looked up once; with the
+    /// `parallel` feature distinct terms are looked up on rayon's thread pool.
+    pub fn lookup_batch<S: AsRef<str> + Sync>(
+        &self,
+        terms: &[S],
+        max_distance: u8,
+        verbosity: Verbosity,
+    ) -> Vec<Vec<Suggestion>> {
+        Lookup::lookup_batch(self, terms, max_distance, verbosity)
+    }
+
+    /// Iterate over the words of `text` that are not in the dictionary, with
+    /// their byte/char offsets, line/column and suggestions.
+    pub fn check_text<'a>(&'a self, text: &'a str) -> CheckText<'a, Self> {
+        Lookup::check_text(self, text)
+    }
+
+    /// Check the subwords of a `camelCase`, `snake_case`, ... identifier and
+    /// suggest corrections in the same convention (`parseHttpRespnse` ->
+    /// `parseHttpResponse`).
+    pub fn check_identifier(&self, identifier: &str) -> IdentifierCheck {
+        Lookup::check_identifier(self, identifier)
+    }
+
+    /// Correct a whole phrase, merging split words and splitting run-together
+    /// ones (`"whereis th elove"` -> `"where is the love"`).
+    pub fn lookup_compound(&self, input: &str, max_distance: u8) -> Suggestion {
+        Lookup::lookup_compound(self, input, max_distance)
+    }
+
+    /// Insert missing spaces and correct each segment
+    /// (`"thequickbrownfox"` -> `"the quick brown fox"`).
+    pub fn word_segmentation(&self, input: &str, max_distance: u8) -> Segmentation {
+        Lookup::word_segmentation(self, input, max_distance)
+    }
+
+    /// Length in characters of the shortest and longest dictionary word, or
+    /// `None` while the dictionary is empty.
+    pub fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.word_lengths
+    }
+}
+
+impl DeleteIndex for SymSpell {
+    fn word(&self, term: &str) -> Option<&str> {
+        self.dictionary.get_key_value(term).map(|(k, _)| k.as_str())
+    }
+
+    fn frequency(&self, word: &str) -> usize {
+        self.dictionary.get(word).copied().unwrap_or(0)
+    }
+
+    fn for_each_origin<'a>(&'a self, deletion: &str, f: impl FnMut(&'a str)) {
+        if let Some(set) = self.deletes.get(deletion) {
+            set.iter().map(String::as_str).for_each(f);
+        }
     }
 }
 
@@ -263,6 +553,10 @@ pub struct EmbeddedSymSpell {
     pub dict: &'static ::phf::Map<&'static str, usize>,
     /// delete-index map: deletion_variant -> slice of originating words
     pub deletes: &'static ::phf::Map<&'static str, &'static [&'static str]>,
+    scoring: Scoring,
+    word_lengths: Option<(usize, usize)>,
+    fallback: Fallback,
+    length_buckets: Option<&'static [&'static [&'static str]]>,
 }
 
 impl EmbeddedSymSpell {
@@ -280,146 +574,261 @@ impl EmbeddedSymSpell {
             max_distance,
             dict,
             deletes,
+            scoring: Scoring::default(),
+            word_lengths: None,
+            fallback: Fallback::default(),
+            length_buckets: None,
         }
     }
 
-    /// Get frequency from the embedded dict
+    /// Choose what `lookup` does when the deletion index yields no suggestion.
+    pub fn with_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// The fallback used by `lookup`.
+    pub fn fallback(&self) -> Fallback {
+        self.fallback
+    }
+
+    /// Provide the dictionary words grouped by length in characters
+    /// (`buckets[n]` holds every word of length `n`), used by
+    /// `Fallback::LengthBucketed`.
+    ///
+    /// `include_dictionary!` emits these as `WORDS_BY_LEN`.
+    pub fn with_length_buckets(mut self, buckets: &'static [&'static [&'static str]]) -> Self {
+        self.length_buckets = Some(buckets);
+        self
+    }
+
+    /// Record the length in characters of the shortest and longest word in
+    /// `dict`, letting `lookup` reject queries no word can be within reach of.
+    ///
+    /// `include_dictionary!` emits these as `MIN_WORD_LEN` / `MAX_WORD_LEN`.
+    pub fn with_word_lengths(mut self, min: usize, max: usize) -> Self {
+        self.word_lengths = Some((min, max));
+        self
+    }
+
+    /// Length in characters of the shortest and longest word, if known.
+    pub fn word_lengths(&self) -> Option<(usize, usize)> {
+        self.word_lengths
+    }
+
+    /// Use `metric` to verify and rank candidates instead of the default
+    /// optimal string alignment distance.
+    pub fn with_metric<M: DistanceMetric + 'static>(mut self, metric: M) -> Self {
+        self.scoring.metric = Arc::new(metric);
+        self
+    }
+
+    /// The distance metric used by `lookup`.
+    pub fn metric(&self) -> &dyn DistanceMetric {
+        &*self.scoring.metric
+    }
+
+    /// The distance metric, shared rather than borrowed.
+    pub(crate) fn shared_metric(&self) -> Arc<dyn DistanceMetric> {
+        Arc::clone(&self.scoring.metric)
+    }
+
+    /// Rank candidates with the same `distance` by their weighted edit cost
+    /// (lowest first) before falling back to frequency.
+    pub fn with_edit_costs(mut self, costs: EditCosts) -> Self {
+        self.scoring.edit_costs = Some(Arc::new(costs));
+        self
+    }
+
+    /// The weighted edit costs used for ranking, if any.
+    pub fn edit_costs(&self) -> Option<&EditCosts> {
+        self.scoring.edit_costs.as_deref()
+    }
+
+    /// Never suggest the words of `blocklist` (see the `blocklist` module).
+    ///
+    /// `include_dictionary!` emits one for `exclude` with `keep_excluded = true`.
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.scoring.blocklist = Some(Arc::new(blocklist));
+        self
+    }
+
+    /// The blocklist, if any.
+    pub fn blocklist(&self) -> Option<&Blocklist> {
+        self.scoring.blocklist.as_deref()
+    }
+
+    /// Consult `corrections` before the deletion index (see the
+    /// `corrections` module).
+    ///
+    /// `include_dictionary!` emits one for its `corrections` option.
+    pub fn with_corrections(mut self, corrections: CorrectionTable) -> Self {
+        self.scoring.corrections = Some(Arc::new(corrections));
+        self
+    }
+
+    /// The correction table, if any.
+    pub fn corrections(&self) -> Option<&CorrectionTable> {
+        self.scoring.corrections.as_deref()
+    }
+
+    /// Rank suggestions by noisy-channel probability instead of distance
+    /// first (see the `noisy` module).
+    pub fn with_noisy_channel(mut self, channel: NoisyChannel) -> Self {
+        self.scoring.noisy_channel = Some(channel);
+        self
+    }
+
+    /// The noisy-channel scoring, if enabled.
+    pub fn noisy_channel(&self) -> Option<NoisyChannel> {
+        self.scoring.noisy_channel
+    }
+
+    /// Get frequency from the embedded dict. Blocked words that are not
+    /// `recognized` have none.
     pub fn frequency(&self, word: &str) -> Option<usize> {
-        self.dict.get(word).copied()
+        self.dict
+            .get(word)
+            .copied()
+            .filter(|_| !self.scoring.rejects(word))
     }
 
     /// Lookup suggestions using the precomputed deletes PHF map.
     ///
     /// Behavior mirrors `SymSpell::lookup`: enumerate deletion-variants of the query
     /// (up to `max_distance`), use the deletes PHF to find candidate original words,
-    /// then verify candidates with Damerau-Levenshtein and return suggestions according
-    /// to `verbosity`.
+    /// then verify candidates with the configured `DistanceMetric` and return
+    /// suggestions according to `verbosity`. If that finds nothing, the
+    /// dictionary is scanned according to the configured `Fallback`.
+    ///
+    /// A `CorrectionTable` hit comes first (see `with_corrections`).
     pub fn lookup(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
         if term.is_empty() {
             return Vec::new();
         }
-        let max_distance = std::cmp::min(max_distance, self.max_distance);
+        let corrected = self.scoring.correction(self, term);
+        if corrected.is_some() && verbosity != Verbosity::All {
+            return corrected.into_iter().collect();
+        }
+        prepend_correction(corrected, self.lookup_index(term, max_distance, verbosity))
+    }
 
-        // If exact match
-        if let Some(&freq) = self.dict.get(term) {
-            return vec![Suggestion {
-                term: term.to_string(),
-                frequency: freq,
-                distance: 0,
-            }];
+    /// `lookup` without the correction table.
+    fn lookup_index(&self, term: &str, max_distance: u8, verbosity: Verbosity) -> Vec<Suggestion> {
+        let max_distance = std::cmp::min(max_distance, self.max_distance);
+        let term_len = term.chars().count();
+        let prune = !self.scoring.has_confusions();
+        if prune && !within_reach(self.word_lengths, term_len, max_distance) {
+            return Vec::new();
         }
 
-        // Candidate words found from the PHF deletion-index
-        let mut candidates: HashSet<String> = HashSet::new();
-        // Track visited deletion variants to avoid duplicate PHF lookups
-        let mut visited_deletions: HashSet<String> = HashSet::new();
+        let mut results = search(
+            self,
+            &self.scoring,
+            term,
+            max_distance,
+            verbosity,
+            self.word_lengths,
+        );
 
-        // Generate deletions up to max_distance (BFS by deletion-levels)
-        let mut queue: Vec<String> = vec![term.to_string()];
-        let queue_limit = 10000usize;
+        if results.is_empty() {
+            results = self.fallback_scan(term, term_len, max_distance, prune);
+        }
 
-        for idx in 0..queue.len() {
-            if idx >= queue_limit {
-                break;
-            }
-            let current = queue[idx].clone();
+        self.scoring.select(results, verbosity)
+    }
 
-            // Avoid repeated PHF lookups for the same deletion variant
-            if visited_deletions.insert(current.clone()) {
-                if let Some(slice) = self.deletes.get(&current as &str) {
-                    for &w in *slice {
-                        candidates.insert(w.to_string());
+    /// Scan the dictionary as configured by `self.fallback`. With `prune`,
+    /// words whose length alone puts them out of reach are skipped.
+    fn fallback_scan(
+        &self,
+        term: &str,
+        term_len: usize,
+        max_distance: u8,
+        prune: bool,
+    ) -> Vec<Ranked> {
+        let mut results = Vec::new();
+        let mut consider = |word: &str, frequency: usize| {
+            if let Some(mut ranked) = self.scoring.verify(term, word, frequency, max_distance) {
+                ranked.suggestion.origin = Origin::FallbackScan;
+                results.push(ranked);
+            }
+        };
+        let reachable =
+            |word: &str| !prune || word.chars().count().abs_diff(term_len) <= max_distance as usize;
+
+        match (self.fallback, self.length_buckets) {
+            (Fallback::Disabled, _) => {}
+            (Fallback::LengthBucketed, Some(buckets)) if prune => {
+                let min = term_len.saturating_sub(max_distance as usize);
+                let max = term_len + max_distance as usize;
+                for &word in buckets.iter().take(max + 1).skip(min).copied().flatten() {
+                    consider(word, self.frequency_or_zero(word));
+                }
+            }
+            (
+                Fallback::Bounded {
+                    max_words,
+                    max_time,
+                },
+                _,
+            ) => {
+                let start = Instant::now();
+                for (i, (&word, &frequency)) in self.dict.entries().take(max_words).enumerate() {
+                    // Reading the clock is not free; check it every few entries.
+                    if i % 64 == 0 && max_time.is_some_and(|t| start.elapsed() >= t) {
+                        break;
+                    }
+                    if reachable(word) {
+                        consider(word, frequency);
                     }
                 }
             }
-
-            // Generate next-level deletions (1-deletions of current)
-            if (current.len() > 1) && (max_distance as usize) > 0 {
-                for i in 0..current.len() {
-                    let mut s = current.clone();
-                    s.remove(i);
-                    if !queue.contains(&s) {
-                        queue.push(s);
+            (Fallback::Full | Fallback::LengthBucketed, _) => {
+                for (&word, &frequency) in self.dict.entries() {
+                    if reachable(word) {
+                        consider(word, frequency);
                     }
                 }
             }
         }
+        results
+    }
 
-        // Compute Damerau-Levenshtein distances for candidates and collect results
-        let mut results: Vec<Suggestion> = Vec::new();
-
-        for cand in candidates {
-            let distance = damerau_levenshtein(term, &cand);
-            if distance <= max_distance {
-                let freq = *self.dict.get(&cand as &str).unwrap_or(&0);
-                results.push(Suggestion {
-                    term: cand.clone(),
-                    frequency: freq,
-                    distance,
-                });
-            }
-        }
+    // Convenience helpers added for easier user-facing API:
 
-        if results.is_empty() {
-            // Fallback: if no candidates were discovered via the precomputed deletes map,
-            // scan the embedded dictionary directly as a last-resort option. This keeps
-            // behavior consistent with a runtime-built SymSpell when the deletes index
-            // might not include helpful variants for short or unusual queries.
-            for (k, &v) in self.dict.entries() {
-                let distance = damerau_levenshtein(term, k);
-                if distance <= max_distance {
-                    results.push(Suggestion {
-                        term: k.to_string(),
-                        frequency: v,
-                        distance,
-                    });
-                }
-            }
+    /// Look up every term of `terms`, returning one suggestion list per term
+    /// in the same order (see `SymSpell::lookup_batch`).
+    pub fn lookup_batch<S: AsRef<str> + Sync>(
+        &self,
+        terms: &[S],
+        max_distance: u8,
+        verbosity: Verbosity,
+    ) -> Vec<Vec<Suggestion>> {
+        Lookup::lookup_batch(self, terms, max_distance, verbosity)
+    }
 
-            if results.is_empty() {
-                return Vec::new();
-            }
-        }
+    /// Iterate over the words of `text` that are not in the dictionary (see
+    /// `SymSpell::check_text`).
+    pub fn check_text<'a>(&'a self, text: &'a str) -> CheckText<'a, Self> {
+        Lookup::check_text(self, text)
+    }
 
-        // Determine minimal distance among results
-        let min_distance = results.iter().map(|r| r.distance).min().unwrap_or(u8::MAX);
+    /// Check the subwords of an identifier (see `SymSpell::check_identifier`).
+    pub fn check_identifier(&self, identifier: &str) -> IdentifierCheck {
+        Lookup::check_identifier(self, identifier)
+    }
 
-        match verbosity {
-            Verbosity::Top => {
-                let mut best: Option<Suggestion> = None;
-                for r in results.into_iter().filter(|r| r.distance == min_distance) {
-                    match &best {
-                        None => best = Some(r),
-                        Some(b) => {
-                            if r.frequency > b.frequency {
-                                best = Some(r);
-                            }
-                        }
-                    }
-                }
-                best.into_iter().collect()
-            }
-            Verbosity::Closest => {
-                let mut filtered: Vec<Suggestion> = results
-                    .into_iter()
-                    .filter(|r| r.distance == min_distance)
-                    .collect();
-                filtered.sort_by(|a, b| b.frequency.cmp(&a.frequency));
-                filtered
-            }
-            Verbosity::All => {
-                // Return all within max_distance sorted by distance asc then frequency desc
-                results.sort_by(|a, b| {
-                    a.distance
-                        .cmp(&b.distance)
-                        .then_with(|| b.frequency.cmp(&a.frequency))
-                });
-                results
-            }
-        }
+    /// Correct a whole phrase (see `SymSpell::lookup_compound`).
+    pub fn lookup_compound(&self, input: &str, max_distance: u8) -> Suggestion {
+        Lookup::lookup_compound(self, input, max_distance)
     }
 
-    // Convenience helpers added for easier user-facing API:
+    /// Insert missing spaces and correct each segment (see
+    /// `SymSpell::word_segmentation`).
+    pub fn word_segmentation(&self, input: &str, max_distance: u8) -> Segmentation {
+        Lookup::word_segmentation(self, input, max_distance)
+    }
 
     /// Return the single best suggestion (if any) for `term`. This is a shorthand
     /// for `lookup(term, self.max_distance, Verbosity::Top)` returning Option.
@@ -439,9 +848,10 @@ impl EmbeddedSymSpell {
         self.lookup(term, self.max_distance, Verbosity::All)
     }
 
-    /// Returns true if a word is present in the embedded dictionary.
+    /// Returns true if a word is present in the embedded dictionary (and not
+    /// rejected by the blocklist).
     pub fn contains(&self, word: &str) -> bool {
-        self.dict.contains_key(word)
+        self.dict.contains_key(word) && !self.scoring.rejects(word)
     }
 
     /// Return a reference to the underlying PHF dictionary map (word -> frequency).
@@ -466,6 +876,398 @@ impl EmbeddedSymSpell {
     }
 }
 
+impl DeleteIndex for EmbeddedSymSpell {
+    fn word(&self, term: &str) -> Option<&str> {
+        self.dict.get_entry(term).map(|(&k, _)| k)
+    }
+
+    fn frequency(&self, word: &str) -> usize {
+        self.frequency_or_zero(word)
+    }
+
+    fn for_each_origin<'a>(&'a self, deletion: &str, f: impl FnMut(&'a str)) {
+        if let Some(slice) = self.deletes.get(deletion) {
+            slice.iter().copied().for_each(f);
+        }
+    }
+}
+
+/// Upper bound on confusion rewrites of a query explored per lookup.
+const MAX_CONFUSION_VARIANTS: usize = 64;
+
+/// Candidate verification and ranking settings shared by `SymSpell` and
+/// `EmbeddedSymSpell`.
+#[derive(Clone)]
+struct Scoring {
+    metric: Arc<dyn DistanceMetric>,
+    edit_costs: Option<Arc<EditCosts>>,
+    blocklist: Option<Arc<Blocklist>>,
+    corrections: Option<Arc<CorrectionTable>>,
+    noisy_channel: Option<NoisyChannel>,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self {
+            metric: Arc::new(OptimalStringAlignment),
+            edit_costs: None,
+            blocklist: None,
+            corrections: None,
+            noisy_channel: None,
+        }
+    }
+}
+
+/// Phonetic code -> words index maintained next to the deletion index.
+#[derive(Clone)]
+struct PhoneticIndex {
+    encoder: Arc<dyn PhoneticEncoder>,
+    codes: HashMap<String, HashSet<String>>,
+}
+
+impl PhoneticIndex {
+    fn insert(&mut self, word: &str) {
+        for code in self.encoder.encode(word) {
+            self.codes.entry(code).or_default().insert(word.to_string());
+        }
+    }
+
+    fn remove(&mut self, word: &str) {
+        for code in self.encoder.encode(word) {
+            if let Some(words) = self.codes.get_mut(&code) {
+                words.remove(word);
+                if words.is_empty() {
+                    self.codes.remove(&code);
+                }
+            }
+        }
+    }
+
+    /// Indexed words sharing at least one code with `term`.
+    fn matches(&self, term: &str) -> HashSet<&str> {
+        self.encoder
+            .encode(term)
+            .iter()
+            .filter_map(|code| self.codes.get(code))
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl Scoring {
+    /// Returns true if `word` must not be suggested.
+    fn blocks(&self, word: &str) -> bool {
+        self.blocklist.as_ref().is_some_and(|b| b.contains(word))
+    }
+
+    /// Returns true if `word` must not count as a dictionary word either.
+    fn rejects(&self, word: &str) -> bool {
+        self.blocklist.as_ref().is_some_and(|b| b.rejects(word))
+    }
+
+    /// The correction-table suggestion for `term`, unless its correction is
+    /// blocked.
+    fn correction<I: DeleteIndex>(&self, index: &I, term: &str) -> Option<Suggestion> {
+        let correction = self.corrections.as_ref()?.get(term)?;
+        if self.blocks(correction) {
+            return None;
+        }
+        Some(Suggestion {
+            term: correction.to_string(),
+            frequency: index.frequency(correction),
+            distance: self.metric.distance(term, correction),
+            origin: Origin::CorrectionTable,
+            dictionary: None,
+            score: None,
+        })
+    }
+
+    /// Returns true if multi-character confusion pairs are configured.
+    fn has_confusions(&self) -> bool {
+        self.edit_costs.as_ref().is_some_and(|c| c.has_confusions())
+    }
+
+    /// The strings whose deletions are searched for `term`: the term itself
+    /// followed by its confusion rewrites, if confusions are configured.
+    fn query_roots(&self, term: &str, max_distance: u8) -> Vec<String> {
+        let mut roots = vec![term.to_string()];
+        if let Some(costs) = self.edit_costs.as_ref().filter(|c| c.has_confusions()) {
+            roots.extend(costs.confusion_variants(term, max_distance, MAX_CONFUSION_VARIANTS));
+        }
+        roots
+    }
+
+    /// Verify `candidate` against `term`, returning it as a ranked suggestion
+    /// if it lies within `max_distance` and is not blocked.
+    ///
+    /// With confusions configured the distance is the smaller of the metric
+    /// distance and the confusion-aware edit count, so `"rnodern"` is 2 edits
+    /// away from `"modem"` rather than 4.
+    fn verify(
+        &self,
+        term: &str,
+        candidate: &str,
+        frequency: usize,
+        max_distance: u8,
+    ) -> Option<Ranked> {
+        if self.blocks(candidate) {
+            return None;
+        }
+        let confusions = self.edit_costs.as_ref().filter(|c| c.has_confusions());
+        let distance = match confusions {
+            // Confusions can bring a candidate within reach that the metric alone
+            // would reject, so both distances are needed in full.
+            Some(costs) => {
+                let d = self
+                    .metric
+                    .distance(term, candidate)
+                    .min(costs.edit_count(term, candidate));
+                (d <= max_distance).then_some(d)?
+            }
+            None => self.metric.distance_within(term, candidate, max_distance)?,
+        };
+        Some(self.ranked(term, candidate, frequency, distance))
+    }
+
+    /// Distance and weighted cost of `candidate` for `term`, without a bound.
+    fn score(&self, term: &str, candidate: &str, frequency: usize) -> Ranked {
+        let mut distance = self.metric.distance(term, candidate);
+        if let Some(costs) = self.edit_costs.as_ref().filter(|c| c.has_confusions()) {
+            distance = distance.min(costs.edit_count(term, candidate));
+        }
+        self.ranked(term, candidate, frequency, distance)
+    }
+
+    fn ranked(&self, term: &str, candidate: &str, frequency: usize, distance: u8) -> Ranked {
+        let cost = self
+            .edit_costs
+            .as_ref()
+            .map_or(0.0, |costs| costs.distance(term, candidate));
+        // Weighted costs make a finer error model than the edit count.
+        let score = self.noisy_channel.map(|channel| {
+            let edits = if self.edit_costs.is_some() {
+                cost
+            } else {
+                distance as f64
+            };
+            channel.score(frequency, edits)
+        });
+        Ranked {
+            suggestion: Suggestion {
+                term: candidate.to_string(),
+                frequency,
+                distance,
+                origin: Origin::EditDistance,
+                dictionary: None,
+                score,
+            },
+            cost,
+        }
+    }
+
+    /// Order `results` and keep the suggestions requested by `verbosity`
+    /// (see `select_suggestions`).
+    fn select(&self, results: Vec<Ranked>, verbosity: Verbosity) -> Vec<Suggestion> {
+        select_suggestions(results, verbosity, self.noisy_channel.is_some())
+    }
+}
+
+/// Upper bound on deletion variants of a query visited per lookup.
+const QUEUE_LIMIT: usize = 10_000;
+
+/// Read access to a deletion index, shared by `SymSpell` and `EmbeddedSymSpell`.
+trait DeleteIndex {
+    /// The stored dictionary word equal to `term`, if any.
+    fn word(&self, term: &str) -> Option<&str>;
+    /// Frequency of a dictionary word (0 if absent).
+    fn frequency(&self, word: &str) -> usize;
+    /// Call `f` with every dictionary word that produces `deletion`.
+    fn for_each_origin<'a>(&'a self, deletion: &str, f: impl FnMut(&'a str));
+}
+
+/// Returns false if no word with a length in `word_lengths` (in characters)
+/// can be within `max_distance` of a `term_len`-character query.
+fn within_reach(word_lengths: Option<(usize, usize)>, term_len: usize, max_distance: u8) -> bool {
+    let max_distance = max_distance as usize;
+    word_lengths
+        .is_none_or(|(min, max)| term_len + max_distance >= min && term_len <= max + max_distance)
+}
+
+/// Candidates verified so far during a `search`.
+struct Candidates<'a, 't, I> {
+    index: &'a I,
+    scoring: &'t Scoring,
+    term: &'t str,
+    term_len: usize,
+    /// Skip candidates whose length alone puts them beyond `bound`.
+    prune: bool,
+    /// Shrink `bound` to the best distance found (Top / Closest).
+    narrow: bool,
+    bound: u8,
+    seen: HashSet<&'a str>,
+    results: Vec<Ranked>,
+}
+
+impl<'a, I: DeleteIndex> Candidates<'a, '_, I> {
+    fn consider(&mut self, word: &'a str) {
+        if !self.seen.insert(word) {
+            return;
+        }
+        // The bound only ever shrinks, so a candidate skipped here never
+        // needs to be looked at again.
+        if self.prune && word.chars().count().abs_diff(self.term_len) > self.bound as usize {
+            return;
+        }
+        let frequency = self.index.frequency(word);
+        if let Some(ranked) = self.scoring.verify(self.term, word, frequency, self.bound) {
+            if self.narrow {
+                self.bound = self.bound.min(ranked.suggestion.distance);
+            }
+            self.results.push(ranked);
+        }
+    }
+}
+
+/// Symmetric-delete search for `term` in `index`, returning every verified
+/// candidate within `max_distance` that may be selected for `verbosity`.
+///
+/// Deletions of the query are visited level by level and candidates are
+/// verified as soon as they are found, which allows pruning:
+/// - queries more than `max_distance` shorter or longer than every word in
+///   `word_lengths` are rejected up front;
+/// - candidates whose length differs from the query by more than the current
+///   bound are skipped without running the metric;
+/// - for `Top` / `Closest` the bound shrinks to the best distance found and
+///   the search stops once the deletion depth exceeds it, because a word `d`
+///   edits away shares a deletion at most `d` deletes from the query.
+///
+/// Length pruning assumes the metric is at least the length difference, and
+/// is disabled with confusion pairs, which may change the length by more than
+/// one per edit.
+fn search<I: DeleteIndex>(
+    index: &I,
+    scoring: &Scoring,
+    term: &str,
+    max_distance: u8,
+    verbosity: Verbosity,
+    word_lengths: Option<(usize, usize)>,
+) -> Vec<Ranked> {
+    let prune = !scoring.has_confusions();
+    let term_len = term.chars().count();
+    if prune && !within_reach(word_lengths, term_len, max_distance) {
+        return Vec::new();
+    }
+
+    let mut candidates = Candidates {
+        index,
+        scoring,
+        term,
+        term_len,
+        prune,
+        // Noisy-channel `Top` may pick a farther candidate, so it needs them all.
+        narrow: match verbosity {
+            Verbosity::All => false,
+            Verbosity::Top => scoring.noisy_channel.is_none(),
+            Verbosity::Closest => true,
+        },
+        bound: max_distance,
+        seen: HashSet::new(),
+        results: Vec::new(),
+    };
+
+    // Level 0 holds the term itself plus any confusion rewrites of it (see
+    // `EditCosts::with_confusion`); level `n` holds their `n`-deletions.
+    let mut level: Vec<String> = Vec::new();
+    let mut queued: HashSet<String> = HashSet::new();
+    for root in scoring.query_roots(term, max_distance) {
+        if queued.insert(root.clone()) {
+            level.push(root);
+        }
+    }
+
+    let mut visited = 0usize;
+    'levels: for depth in 0..=max_distance {
+        if prune && depth > candidates.bound {
+            break;
+        }
+        let mut next: Vec<String> = Vec::new();
+        for current in &level {
+            if visited == QUEUE_LIMIT {
+                break 'levels;
+            }
+            visited += 1;
+
+            // A deletion of the query may itself be a dictionary word.
+            if let Some(word) = index.word(current) {
+                candidates.consider(word);
+            }
+            index.for_each_origin(current, |word| candidates.consider(word));
+
+            if depth < max_distance && current.chars().nth(1).is_some() {
+                for deletion in single_deletes(current) {
+                    if queued.insert(deletion.clone()) {
+                        next.push(deletion);
+                    }
+                }
+            }
+        }
+        level = next;
+    }
+
+    candidates.results
+}
+
+/// A verified suggestion with its weighted edit cost (0 without `EditCosts`).
+struct Ranked {
+    suggestion: Suggestion,
+    cost: f64,
+}
+
+/// `suggestions` behind the correction-table hit, if any, which replaces a
+/// suggestion of the same term.
+fn prepend_correction(
+    corrected: Option<Suggestion>,
+    mut suggestions: Vec<Suggestion>,
+) -> Vec<Suggestion> {
+    if let Some(corrected) = corrected {
+        suggestions.retain(|s| s.term != corrected.term);
+        suggestions.insert(0, corrected);
+    }
+    suggestions
+}
+
+/// Order `results` by distance asc, weighted cost asc, frequency desc and term
+/// (by noisy-channel score desc first if `noisy`), then keep the suggestions
+/// requested by `verbosity`. `Closest` keeps the smallest distance either way.
+fn select_suggestions(
+    mut results: Vec<Ranked>,
+    verbosity: Verbosity,
+    noisy: bool,
+) -> Vec<Suggestion> {
+    results.sort_by(|a, b| {
+        let by_score = match (a.suggestion.score, b.suggestion.score) {
+            (Some(sa), Some(sb)) if noisy => sb.total_cmp(&sa),
+            _ => std::cmp::Ordering::Equal,
+        };
+        by_score
+            .then_with(|| a.suggestion.distance.cmp(&b.suggestion.distance))
+            .then_with(|| a.cost.total_cmp(&b.cost))
+            .then_with(|| b.suggestion.frequency.cmp(&a.suggestion.frequency))
+            .then_with(|| a.suggestion.term.cmp(&b.suggestion.term))
+    });
+
+    let Some(min_distance) = results.iter().map(|r| r.suggestion.distance).min() else {
+        return Vec::new();
+    };
+    match verbosity {
+        Verbosity::Top => results.truncate(1),
+        Verbosity::Closest => results.retain(|r| r.suggestion.distance == min_distance),
+        Verbosity::All => {}
+    }
+    results.into_iter().map(|r| r.suggestion).collect()
+}
+
 /* Repository-level git-hook helper removed from the library source.
 
    Git hooks are repository maintenance scripts and should live in the
@@ -492,9 +1294,7 @@ fn generate_deletes(word: &str, max_distance: u8) -> HashSet<String> {
             if s.is_empty() {
                 continue;
             }
-            for i in 0..s.len() {
-                let mut t = s.clone();
-                t.remove(i);
+            for t in single_deletes(s) {
                 if deletes.insert(t.clone()) {
                     next.insert(t);
                 }
@@ -508,55 +1308,14 @@ fn generate_deletes(word: &str, max_distance: u8) -> HashSet<String> {
     deletes
 }
 
-/// Damerau-Levenshtein distance with transposition, returns distance as u8.
-///
-/// The implementation is a standard dynamic programming approach. It is not
-/// optimized for speed but is simple and correct. Distances larger than 255
-/// will be capped at 255.
-fn damerau_levenshtein(a: &str, b: &str) -> u8 {
-    let a_chars: Vec<char> = a.chars().collect();
-    let b_chars: Vec<char> = b.chars().collect();
-    let (alen, blen) = (a_chars.len(), b_chars.len());
-
-    if alen == 0 {
-        return blen.min(255) as u8;
-    }
-    if blen == 0 {
-        return alen.min(255) as u8;
-    }
-
-    let mut dp: Vec<Vec<usize>> = vec![vec![0; blen + 1]; alen + 1];
-
-    for i in 0..=alen {
-        dp[i][0] = i;
-    }
-    for j in 0..=blen {
-        dp[0][j] = j;
-    }
-
-    for i in 1..=alen {
-        for j in 1..=blen {
-            let cost = if a_chars[i - 1] == b_chars[j - 1] {
-                0
-            } else {
-                1
-            };
-            dp[i][j] = std::cmp::min(
-                std::cmp::min(dp[i - 1][j] + 1, dp[i][j - 1] + 1),
-                dp[i - 1][j - 1] + cost,
-            );
-            // transposition
-            if i > 1
-                && j > 1
-                && a_chars[i - 1] == b_chars[j - 2]
-                && a_chars[i - 2] == b_chars[j - 1]
-            {
-                dp[i][j] = std::cmp::min(dp[i][j], dp[i - 2][j - 2] + 1);
-            }
-        }
-    }
-
-    dp[alen][blen].min(255) as u8
+/// All strings obtained by removing exactly one character from `s`.
+fn single_deletes(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.char_indices().map(move |(i, c)| {
+        let mut t = String::with_capacity(s.len());
+        t.push_str(&s[..i]);
+        t.push_str(&s[i + c.len_utf8()..]);
+        t
+    })
 }
 
 #[cfg(test)]
@@ -565,9 +1324,10 @@ mod tests {
 
     #[test]
     fn test_damerau_basic() {
-        assert_eq!(damerau_levenshtein("abc", "abc"), 0);
-        assert_eq!(damerau_levenshtein("abc", "ab"), 1);
-        assert_eq!(damerau_levenshtein("ab", "ba"), 1); // transposition
+        let osa = OptimalStringAlignment;
+        assert_eq!(osa.distance("abc", "abc"), 0);
+        assert_eq!(osa.distance("abc", "ab"), 1);
+        assert_eq!(osa.distance("ab", "ba"), 1); // transposition
     }
 
     #[test]
@@ -583,4 +1343,219 @@ mod tests {
         // Expect "hello" to be a top suggestion
         assert!(suggestions.iter().any(|s| s.term == "hello"));
     }
+
+    #[test]
+    fn test_symspell_custom_metric() {
+        use crate::distance::Levenshtein;
+
+        let entries = vec![("form".to_string(), 10usize), ("from".to_string(), 5usize)];
+        let osa = SymSpell::from_iter(2, entries.clone());
+        let lev = SymSpell::from_iter(2, entries).with_metric(Levenshtein);
+
+        // "form" is one transposition away under OSA but two edits under Levenshtein.
+        let top = osa.lookup("fomr", 2, Verbosity::Closest);
+        assert_eq!(top[0].distance, 1);
+        let top = lev.lookup("fomr", 2, Verbosity::Closest);
+        assert_eq!(top[0].distance, 2);
+    }
+
+    #[test]
+    fn test_symspell_edit_costs_break_ties() {
+        use crate::keyboard::KeyboardLayout;
+
+        // "tent" is more frequent, but 'a' sits next to 's' on QWERTY.
+        let entries = vec![("test".to_string(), 1usize), ("tent".to_string(), 100usize)];
+        let plain = SymSpell::from_iter(2, entries.clone());
+        let weighted = SymSpell::from_iter(2, entries)
+            .with_edit_costs(EditCosts::new().with_keyboard(KeyboardLayout::Qwerty, 0.5));
+
+        assert_eq!(plain.lookup("teat", 2, Verbosity::Top)[0].term, "tent");
+        let closest = weighted.lookup("teat", 2, Verbosity::Closest);
+        assert_eq!(closest[0].term, "test");
+        assert_eq!(closest[0].distance, 1);
+        assert_eq!(closest[1].term, "tent");
+    }
+
+    #[test]
+    fn test_symspell_ocr_confusions() {
+        let entries = vec![
+            ("modem".to_string(), 5usize),
+            ("modern".to_string(), 50usize),
+            ("dog".to_string(), 10usize),
+        ];
+        let plain = SymSpell::from_iter(2, entries.clone());
+        let ocr = SymSpell::from_iter(2, entries).with_edit_costs(EditCosts::ocr());
+
+        // "rnodem" is "modem" with 'm' misread as "rn": 2 plain edits but 1 confusion.
+        let top = plain.lookup("rnodem", 2, Verbosity::Top);
+        assert_eq!(top[0].distance, 2);
+        let top = ocr.lookup("rnodem", 2, Verbosity::Top);
+        assert_eq!(top[0].term, "modem");
+        assert_eq!(top[0].distance, 1);
+
+        // Two confusions are beyond plain reach but within two OCR edits.
+        assert!(plain.lookup("rnoclern", 2, Verbosity::All).is_empty());
+        let all = ocr.lookup("rnoclern", 2, Verbosity::All);
+        assert_eq!(all[0].term, "modern");
+        assert_eq!(all[0].distance, 2);
+    }
+
+    #[test]
+    fn test_symspell_phonetic_lookup() {
+        use crate::phonetic::DoubleMetaphone;
+
+        let entries = vec![
+            ("phonetic".to_string(), 10usize),
+            ("fanatic".to_string(), 5usize),
+            ("poetic".to_string(), 20usize),
+        ];
+        let sym = SymSpell::from_iter(2, entries).with_phonetic(DoubleMetaphone::default());
+
+        // "fonetik" is 3 edits from "phonetic" but sounds the same.
+        assert!(sym.lookup("fonetik", 2, Verbosity::All).is_empty());
+        let top = sym.lookup_phonetic("fonetik", 2, Verbosity::Top);
+        assert_eq!(top[0].term, "phonetic");
+        assert_eq!(top[0].origin, Origin::Phonetic);
+        assert_eq!(top[0].distance, 3);
+
+        // Edit-distance matches come first; sound-alikes found both ways are marked.
+        let all = sym.lookup_phonetic("fanetic", 2, Verbosity::All);
+        assert_eq!(all[0].term, "fanatic");
+        assert_eq!(all[0].origin, Origin::EditDistanceAndPhonetic);
+        assert!(all
+            .iter()
+            .any(|s| s.term == "phonetic" && s.origin != Origin::EditDistance));
+    }
+
+    #[test]
+    fn test_symspell_phonetic_lookup_with_corrections() {
+        use crate::phonetic::Soundex;
+
+        let sym = SymSpell::from_iter(2, [("the", 100usize), ("three", 10)])
+            .with_phonetic(Soundex)
+            .with_corrections([("thr", "three")].into_iter().collect::<CorrectionTable>());
+
+        // The table hit keeps its origin and is the only Top/Closest suggestion.
+        for verbosity in [Verbosity::Top, Verbosity::Closest] {
+            let found = sym.lookup_phonetic("thr", 2, verbosity);
+            assert_eq!(found.len(), 1);
+            assert_eq!(
+                (found[0].term.as_str(), found[0].origin),
+                ("three", Origin::CorrectionTable)
+            );
+        }
+        let all = sym.lookup_phonetic("thr", 2, Verbosity::All);
+        assert_eq!(all[0].origin, Origin::CorrectionTable);
+        assert_eq!(all.iter().filter(|s| s.term == "three").count(), 1);
+        assert!(all.iter().any(|s| s.term == "the"));
+    }
+
+    #[test]
+    fn test_symspell_length_pruning() {
+        let entries = vec![
+            ("cat".to_string(), 10usize),
+            ("cart".to_string(), 5usize),
+            ("carton".to_string(), 1usize),
+        ];
+        let sym = SymSpell::from_iter(2, entries);
+        assert_eq!(sym.word_lengths(), Some((3, 6)));
+
+        // Longer than every word by more than `max_distance`.
+        assert!(sym.lookup("cartonnage", 2, Verbosity::All).is_empty());
+        assert!(!within_reach(Some((3, 6)), 9, 2));
+        assert!(within_reach(Some((3, 6)), 8, 2));
+        assert!(!within_reach(Some((4, 6)), 1, 2));
+        assert!(within_reach(None, 100, 0));
+
+        // An exact match ends a Top search without considering longer words.
+        let top = sym.lookup("cart", 2, Verbosity::Top);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].distance, 0);
+    }
+
+    #[test]
+    fn test_symspell_pruned_matches_all() {
+        let words = [
+            "the", "then", "than", "them", "there", "three", "throw", "threw", "tree", "trees",
+            "street", "stream", "strain", "stain", "satin", "saint", "paint", "pain", "plain",
+            "plan", "clan", "can", "cane", "crane", "crate", "create", "created", "creative",
+        ];
+        let sym = SymSpell::from_iter(2, words.iter().map(|w| (w.to_string(), w.len())));
+        let queries = [
+            "thn", "ther", "thre", "tre", "streat", "stian", "satni", "pian", "plian", "cn",
+            "cran", "craete", "creatd", "creativ", "xyz", "theree",
+        ];
+        for q in queries {
+            let all = sym.lookup(q, 2, Verbosity::All);
+            let closest = sym.lookup(q, 2, Verbosity::Closest);
+            let top = sym.lookup(q, 2, Verbosity::Top);
+            let min = all.first().map(|s| s.distance);
+            let expected: Vec<Suggestion> = all
+                .iter()
+                .take_while(|s| Some(s.distance) == min)
+                .cloned()
+                .collect();
+            assert_eq!(closest, expected, "closest for {q}");
+            assert_eq!(top.first(), all.first(), "top for {q}");
+        }
+    }
+
+    #[test]
+    fn test_symspell_non_ascii_deletes() {
+        let entries = vec![("café".to_string(), 3usize), ("naïve".to_string(), 2usize)];
+        let sym = SymSpell::from_iter(2, entries);
+        assert_eq!(sym.word_lengths(), Some((4, 5)));
+        assert_eq!(sym.lookup("cafe", 2, Verbosity::Top)[0].term, "café");
+        assert_eq!(sym.lookup("naïv", 2, Verbosity::Top)[0].term, "naïve");
+        assert!(generate_deletes("é", 1).contains(""));
+    }
+
+    #[test]
+    fn test_symspell_multi_level_deletes() {
+        // "apple" is only reachable by deleting two characters of the query.
+        let sym = SymSpell::from_iter(2, [("apple", 10usize)]);
+        for verbosity in [Verbosity::Top, Verbosity::Closest, Verbosity::All] {
+            let suggestions = sym.lookup("applzez", 2, verbosity);
+            assert_eq!(suggestions.len(), 1);
+            assert_eq!(
+                (suggestions[0].term.as_str(), suggestions[0].distance),
+                ("apple", 2)
+            );
+        }
+        assert!(sym.lookup("applzez", 1, Verbosity::All).is_empty());
+
+        // Multibyte queries delete whole chars instead of panicking.
+        let sym = SymSpell::from_iter(2, [("naïve", 2usize), ("ça", 1)]);
+        assert_eq!(sym.lookup("naïveéé", 2, Verbosity::Top)[0].term, "naïve");
+        assert_eq!(sym.lookup("çaé", 2, Verbosity::Top)[0].term, "ça");
+        assert!(sym.lookup("日本語", 2, Verbosity::All).is_empty());
+    }
+
+    #[test]
+    fn test_symspell_remove() {
+        use crate::phonetic::Soundex;
+
+        let entries = vec![
+            ("cat".to_string(), 10usize),
+            ("cart".to_string(), 5usize),
+            ("carton".to_string(), 1usize),
+        ];
+        let mut sym = SymSpell::from_iter(2, entries).with_phonetic(Soundex);
+        let before = sym.deletes.len();
+        sym.load_iter([("carts", 2usize)]);
+
+        assert_eq!(sym.remove("carts"), Some(2));
+        assert_eq!(sym.remove("carts"), None);
+        assert_eq!(sym.deletes.len(), before);
+        assert_eq!(sym.len(), 3);
+
+        assert_eq!(sym.remove("carton"), Some(1));
+        assert_eq!(sym.word_lengths(), Some((3, 4)));
+        assert!(sym.deletes.values().all(|words| !words.contains("carton")));
+        assert!(sym
+            .lookup_phonetic("kartn", 2, Verbosity::All)
+            .iter()
+            .all(|s| s.term != "carton"));
+        assert_eq!(sym.lookup("carto", 2, Verbosity::Top)[0].term, "cart");
+    }
 }
@@ -0,0 +1,322 @@
+/*!
+text module
+
+Spell-checking of whole documents. `Lookup::check_text` splits text into
+words, skips words found in the dictionary and yields a `Misspelling` for each
+remaining word with its position (byte and character offsets, 1-based line and
+column) and ranked suggestions, so editors can underline and replace it in
+place.
+
+Words are produced by a `Tokenizer` (by default `WordTokenizer`, which skips
+URLs, e-mail addresses, numbers, hex strings and acronyms). With `ignore_case`
+(the default) a word is known if it or its lower-case form is in the dictionary,
+and suggestions for capitalised or upper-case words are looked up in lower
+case and returned in the word's casing (`"Helo"` -> `"Hello"`).
+*/
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::lookup::Lookup;
+use crate::symspell::{Suggestion, Verbosity};
+use crate::tokenize::{Tokenizer, WordTokenizer};
+
+/// A word of the checked text that is not in the dictionary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misspelling {
+    /// The word as it appears in the text.
+    pub token: String,
+    /// Byte offsets of the word in the text.
+    pub byte_range: Range<usize>,
+    /// Character (Unicode scalar value) offsets of the word in the text.
+    pub char_range: Range<usize>,
+    /// 1-based line of the first character.
+    pub line: usize,
+    /// 1-based column of the first character, counted in characters.
+    pub column: usize,
+    /// Ranked replacements, in the casing of `token`.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Settings for `Lookup::check_text_with`.
+#[derive(Clone)]
+pub struct CheckOptions {
+    /// Maximum edit distance of suggestions (`None`: the index maximum).
+    pub max_distance: Option<u8>,
+    /// Which suggestions to return for each misspelling.
+    pub verbosity: Verbosity,
+    /// Keep at most this many suggestions per misspelling.
+    pub max_suggestions: Option<usize>,
+    /// Accept words whose lower-case form is known and adapt suggestion casing.
+    pub ignore_case: bool,
+    /// Splits the text into words.
+    pub tokenizer: Arc<dyn Tokenizer>,
+}
+
+impl fmt::Debug for CheckOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckOptions")
+            .field("max_distance", &self.max_distance)
+            .field("verbosity", &self.verbosity)
+            .field("max_suggestions", &self.max_suggestions)
+            .field("ignore_case", &self.ignore_case)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: None,
+            verbosity: Verbosity::Closest,
+            max_suggestions: Some(5),
+            ignore_case: true,
+            tokenizer: Arc::new(WordTokenizer::default()),
+        }
+    }
+}
+
+impl CheckOptions {
+    /// Default options: closest suggestions (at most 5), case-insensitive,
+    /// default `WordTokenizer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit suggestions to `max_distance` edits.
+    pub fn with_max_distance(mut self, max_distance: u8) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Choose which suggestions to return.
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Keep at most `max_suggestions` suggestions (`None`: no limit).
+    pub fn with_max_suggestions(mut self, max_suggestions: Option<usize>) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Treat words case-insensitively.
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Split text into words with `tokenizer`.
+    pub fn with_tokenizer<T: Tokenizer + 'static>(mut self, tokenizer: T) -> Self {
+        self.tokenizer = Arc::new(tokenizer);
+        self
+    }
+}
+
+/// Iterator over the misspellings of a text, created by `Lookup::check_text`.
+pub struct CheckText<'a, L> {
+    dictionary: &'a L,
+    text: &'a str,
+    options: CheckOptions,
+    words: std::vec::IntoIter<Range<usize>>,
+    cursor: Cursor,
+}
+
+/// Scan position in the text.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    byte: usize,
+    char: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a, L: Lookup> CheckText<'a, L> {
+    pub(crate) fn new(dictionary: &'a L, text: &'a str, options: CheckOptions) -> Self {
+        let words = options.tokenizer.tokenize(text).into_iter();
+        Self {
+            dictionary,
+            text,
+            options,
+            words,
+            cursor: Cursor {
+                byte: 0,
+                char: 0,
+                line: 1,
+                column: 1,
+            },
+        }
+    }
+
+    /// Move the cursor forward to byte offset `to`.
+    fn advance(&mut self, to: usize) {
+        for c in self.text[self.cursor.byte..to].chars() {
+            self.cursor.char += 1;
+            if c == '\n' {
+                self.cursor.line += 1;
+                self.cursor.column = 1;
+            } else {
+                self.cursor.column += 1;
+            }
+        }
+        self.cursor.byte = to;
+    }
+
+    fn is_known(&self, token: &str) -> bool {
+        self.dictionary.contains(token)
+            || (self.options.ignore_case && self.dictionary.contains(&token.to_lowercase()))
+    }
+
+    fn suggest(&self, token: &str) -> Vec<Suggestion> {
+        let max_distance = self
+            .options
+            .max_distance
+            .unwrap_or_else(|| self.dictionary.max_distance());
+        let mut suggestions = if self.options.ignore_case {
+            let mut found =
+                self.dictionary
+                    .lookup(&token.to_lowercase(), max_distance, self.options.verbosity);
+            for s in &mut found {
+                s.term = match_case(token, &s.term);
+            }
+            found
+        } else {
+            self.dictionary
+                .lookup(token, max_distance, self.options.verbosity)
+        };
+        if let Some(limit) = self.options.max_suggestions {
+            suggestions.truncate(limit);
+        }
+        suggestions
+    }
+}
+
+impl<L: Lookup> Iterator for CheckText<'_, L> {
+    type Item = Misspelling;
+
+    fn next(&mut self) -> Option<Misspelling> {
+        while let Some(word) = self.words.next() {
+            self.advance(word.start);
+            let start = self.cursor;
+            self.advance(word.end);
+            let token = &self.text[word.clone()];
+            if self.is_known(token) {
+                continue;
+            }
+            return Some(Misspelling {
+                token: token.to_string(),
+                byte_range: word,
+                char_range: start.char..self.cursor.char,
+                line: start.line,
+                column: start.column,
+                suggestions: self.suggest(token),
+            });
+        }
+        self.advance(self.text.len());
+        None
+    }
+}
+
+/// `word` in the casing of `pattern`: upper case if `pattern` is upper case
+/// (and longer than one character), capitalised if `pattern` is, else as is.
+pub(crate) fn match_case(pattern: &str, word: &str) -> String {
+    let mut letters = pattern.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return word.to_string();
+    };
+    let mut rest = letters.peekable();
+    if first.is_uppercase() && rest.peek().is_some() && rest.all(char::is_uppercase) {
+        return word.to_uppercase();
+    }
+    if first.is_uppercase() {
+        let mut chars = word.chars();
+        return match chars.next() {
+            Some(c) => c.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+    word.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symspell::SymSpell;
+    use crate::tokenize::IgnoreRule;
+
+    fn dictionary() -> SymSpell {
+        let words = [
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog", "café",
+        ];
+        SymSpell::from_iter(2, words.iter().map(|w| (*w, 10usize)))
+    }
+
+    #[test]
+    fn test_check_text_positions() {
+        let sym = dictionary();
+        let text = "The quikc brown fox\njumps óver the lazzy dog.";
+        let found: Vec<Misspelling> = sym.check_text(text).collect();
+        let tokens: Vec<&str> = found.iter().map(|m| m.token.as_str()).collect();
+        assert_eq!(tokens, ["quikc", "óver", "lazzy"]);
+
+        assert_eq!(found[0].byte_range, 4..9);
+        assert_eq!((found[0].line, found[0].column), (1, 5));
+        assert_eq!(found[0].suggestions[0].term, "quick");
+
+        // "ó" is two bytes: byte and char offsets diverge after it.
+        assert_eq!(found[1].byte_range, 26..31);
+        assert_eq!(found[1].char_range, 26..30);
+        assert_eq!((found[1].line, found[1].column), (2, 7));
+        assert_eq!(&text[found[2].byte_range.clone()], "lazzy");
+        assert_eq!(found[2].char_range, 35..40);
+        assert_eq!((found[2].line, found[2].column), (2, 16));
+    }
+
+    #[test]
+    fn test_check_text_casing() {
+        let sym = dictionary();
+        // All-caps words are acronyms to the default tokenizer; keep them here.
+        let tokenizer = WordTokenizer::new().with_ignore(IgnoreRule::Acronym, false);
+        let options = CheckOptions::new().with_tokenizer(tokenizer);
+        let found: Vec<Misspelling> = sym.check_text_with("Quikc FOXX Café", options).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].suggestions[0].term, "Quick");
+        assert_eq!(found[1].suggestions[0].term, "FOX");
+
+        let strict = CheckOptions::new().with_ignore_case(false);
+        let found: Vec<Misspelling> = sym.check_text_with("The dog", strict).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].token, "The");
+    }
+
+    #[test]
+    fn test_check_text_tokenizer() {
+        let sym = dictionary();
+        let text = "The NASA dgo jumps over https://example.com the quikc-brown fox";
+        let found: Vec<String> = sym.check_text(text).map(|m| m.token).collect();
+        assert_eq!(found, ["dgo", "quikc"]);
+
+        let options = CheckOptions::new().with_tokenizer(
+            WordTokenizer::new()
+                .with_ignore(IgnoreRule::Acronym, false)
+                .with_custom_rule(|word| word == "dgo"),
+        );
+        let found: Vec<String> = sym
+            .check_text_with(text, options)
+            .map(|m| m.token)
+            .collect();
+        assert_eq!(found, ["NASA", "quikc"]);
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("Helo", "hello"), "Hello");
+        assert_eq!(match_case("HELO", "hello"), "HELLO");
+        assert_eq!(match_case("helo", "hello"), "hello");
+        assert_eq!(match_case("I", "it"), "It");
+        assert_eq!(match_case("éte", "été"), "été");
+        assert_eq!(match_case("Éte", "été"), "Été");
+    }
+}
@@ -0,0 +1,346 @@
+/*!
+tokenize module
+
+Splitting text into the words checked by `check_text`.
+
+`Tokenizer` is the extension point; `WordTokenizer` is the default. It splits
+text on whitespace into chunks, drops chunks that are URLs or e-mail
+addresses, then splits each chunk into words:
+
+- a word is a run of letters and digits, which may contain apostrophes
+  (`isn't`, `O’Brien`) and, unless hyphenated words are split, hyphens;
+- surrounding punctuation and quotes are not part of the word;
+- a possessive `'s` is stripped (`dog's` -> `dog`);
+- words that are numbers, hex strings or all-caps acronyms are dropped.
+
+Each built-in rule can be turned off with `WordTokenizer::with_ignore`, and
+custom rules can be added with `with_custom_rule`.
+*/
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Splits text into the words to be spell-checked.
+pub trait Tokenizer: Send + Sync {
+    /// Byte ranges of the words of `text`, in ascending order.
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// Classes of tokens `WordTokenizer` never reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgnoreRule {
+    /// `https://...`, `file://...`, `www.example.com`.
+    Url,
+    /// `name@example.com`.
+    Email,
+    /// Words starting with a digit: `42`, `3rd`, `10px`, `0x1F`.
+    Number,
+    /// Hex strings of 6+ characters mixing letters and digits, e.g. commit hashes.
+    Hex,
+    /// All-caps words of 2+ letters, optionally plural: `NASA`, `HTTP2`, `APIs`.
+    Acronym,
+}
+
+impl IgnoreRule {
+    /// Every built-in rule.
+    pub const ALL: [IgnoreRule; 5] = [
+        IgnoreRule::Url,
+        IgnoreRule::Email,
+        IgnoreRule::Number,
+        IgnoreRule::Hex,
+        IgnoreRule::Acronym,
+    ];
+
+    /// Returns true if `token` belongs to this class.
+    pub fn matches(&self, token: &str) -> bool {
+        match self {
+            IgnoreRule::Url => is_url(token),
+            IgnoreRule::Email => is_email(token),
+            IgnoreRule::Number => token.starts_with(|c: char| c.is_ascii_digit()),
+            IgnoreRule::Hex => is_hex(token),
+            IgnoreRule::Acronym => is_acronym(token),
+        }
+    }
+}
+
+type CustomRule = dyn Fn(&str) -> bool + Send + Sync;
+
+/// The default `Tokenizer` (see the module docs).
+#[derive(Clone)]
+pub struct WordTokenizer {
+    ignore: HashSet<IgnoreRule>,
+    split_hyphens: bool,
+    strip_possessive: bool,
+    custom: Vec<Arc<CustomRule>>,
+}
+
+impl Default for WordTokenizer {
+    fn default() -> Self {
+        Self {
+            ignore: IgnoreRule::ALL.into_iter().collect(),
+            split_hyphens: true,
+            strip_possessive: true,
+            custom: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for WordTokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WordTokenizer")
+            .field("ignore", &self.ignore)
+            .field("split_hyphens", &self.split_hyphens)
+            .field("strip_possessive", &self.strip_possessive)
+            .field("custom_rules", &self.custom.len())
+            .finish()
+    }
+}
+
+impl WordTokenizer {
+    /// All built-in ignore rules enabled, hyphenated words split, possessives stripped.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable a built-in ignore rule.
+    pub fn with_ignore(mut self, rule: IgnoreRule, enabled: bool) -> Self {
+        if enabled {
+            self.ignore.insert(rule);
+        } else {
+            self.ignore.remove(&rule);
+        }
+        self
+    }
+
+    /// Check the parts of hyphenated words separately (`true`, the default)
+    /// or as a whole.
+    pub fn with_split_hyphens(mut self, split: bool) -> Self {
+        self.split_hyphens = split;
+        self
+    }
+
+    /// Strip a trailing possessive `'s` (the default).
+    pub fn with_strip_possessive(mut self, strip: bool) -> Self {
+        self.strip_possessive = strip;
+        self
+    }
+
+    /// Skip tokens for which `rule` returns true. The rule sees each
+    /// whitespace-delimited chunk as it appears in the text (e.g. `#tag,`) and
+    /// then each word of the chunk.
+    pub fn with_custom_rule<F>(mut self, rule: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.custom.push(Arc::new(rule));
+        self
+    }
+
+    fn is_ignored(&self, token: &str) -> bool {
+        self.ignore.iter().any(|rule| rule.matches(token)) || self.custom.iter().any(|r| r(token))
+    }
+
+    fn is_joiner(&self, c: char) -> bool {
+        matches!(c, '\'' | '’') || (c == '-' && !self.split_hyphens)
+    }
+
+    /// Push the words of the whitespace-free chunk `text[range]`.
+    fn chunk(&self, text: &str, range: Range<usize>, tokens: &mut Vec<Range<usize>>) {
+        let raw = &text[range.clone()];
+        if self.custom.iter().any(|r| r(raw)) {
+            return;
+        }
+        // Trim surrounding punctuation before matching URLs and addresses.
+        let Some(first) = raw.find(char::is_alphanumeric) else {
+            return;
+        };
+        let last = raw
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map_or(raw.len(), |(i, c)| i + c.len_utf8());
+        let trimmed = &raw[first..last];
+        if (self.ignore.contains(&IgnoreRule::Url) && is_url(trimmed))
+            || (self.ignore.contains(&IgnoreRule::Email) && is_email(trimmed))
+        {
+            return;
+        }
+
+        let chars: Vec<(usize, char)> = raw.char_indices().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if !chars[i].1.is_alphanumeric() {
+                i += 1;
+                continue;
+            }
+            let start = chars[i].0;
+            let mut j = i + 1;
+            loop {
+                if j < chars.len() && chars[j].1.is_alphanumeric() {
+                    j += 1;
+                } else if j + 1 < chars.len()
+                    && self.is_joiner(chars[j].1)
+                    && chars[j + 1].1.is_alphanumeric()
+                {
+                    j += 2;
+                } else {
+                    break;
+                }
+            }
+            let end = chars.get(j).map_or(raw.len(), |&(k, _)| k);
+            self.word(text, range.start + start..range.start + end, tokens);
+            i = j;
+        }
+    }
+
+    fn word(&self, text: &str, mut range: Range<usize>, tokens: &mut Vec<Range<usize>>) {
+        if self.strip_possessive {
+            let word = &text[range.clone()];
+            for suffix in ["'s", "’s", "'S", "’S"] {
+                if word.len() > suffix.len() && word.ends_with(suffix) {
+                    range.end -= suffix.len();
+                    break;
+                }
+            }
+        }
+        if !self.is_ignored(&text[range.clone()]) {
+            tokens.push(range);
+        }
+    }
+}
+
+impl Tokenizer for WordTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            if c.is_whitespace() {
+                if let Some(s) = start.take() {
+                    self.chunk(text, s..i, &mut tokens);
+                }
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+        if let Some(s) = start {
+            self.chunk(text, s..text.len(), &mut tokens);
+        }
+        tokens
+    }
+}
+
+fn is_url(token: &str) -> bool {
+    token.contains("://")
+        || token
+            .get(..4)
+            .is_some_and(|p| p.eq_ignore_ascii_case("www."))
+}
+
+fn is_email(token: &str) -> bool {
+    match token.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+        }
+        None => false,
+    }
+}
+
+fn is_hex(token: &str) -> bool {
+    token.len() >= 6
+        && token.chars().all(|c| c.is_ascii_hexdigit())
+        && token.chars().any(|c| c.is_ascii_digit())
+        && token.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+fn is_acronym(token: &str) -> bool {
+    let stem = token.strip_suffix('s').unwrap_or(token);
+    stem.chars().filter(|c| c.is_alphabetic()).count() >= 2
+        && stem.chars().all(|c| c.is_uppercase() || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words<'a>(tokenizer: &WordTokenizer, text: &'a str) -> Vec<&'a str> {
+        tokenizer
+            .tokenize(text)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect()
+    }
+
+    #[test]
+    fn test_word_tokenizer_ignore_rules() {
+        let text = "Visit https://example.com/a-b or mail <bob@example.org>. The NASA APIs' \
+                    3rd build a3f9c2e1 isn't well-known — 'quoted' O’Brien's dog's (HTTP2).";
+        let tokenizer = WordTokenizer::new();
+        assert_eq!(
+            words(&tokenizer, text),
+            [
+                "Visit",
+                "or",
+                "mail",
+                "The",
+                "build",
+                "isn't",
+                "well",
+                "known",
+                "quoted",
+                "O’Brien",
+                "dog"
+            ]
+        );
+
+        let keep_all = IgnoreRule::ALL
+            .into_iter()
+            .fold(WordTokenizer::new(), |t, rule| t.with_ignore(rule, false))
+            .with_split_hyphens(false)
+            .with_strip_possessive(false);
+        let found = words(
+            &keep_all,
+            "see www.rust-lang.org, NASA's well-known 3rd a3f9c2e1",
+        );
+        assert_eq!(
+            found,
+            [
+                "see",
+                "www",
+                "rust-lang",
+                "org",
+                "NASA's",
+                "well-known",
+                "3rd",
+                "a3f9c2e1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_tokenizer_custom_rules() {
+        let tokenizer = WordTokenizer::new()
+            .with_custom_rule(|chunk| chunk.starts_with('#'))
+            .with_custom_rule(|word| word.eq_ignore_ascii_case("symspellrs"));
+        assert_eq!(
+            words(&tokenizer, "#rustlang tags: Symspellrs rocks"),
+            ["tags", "rocks"]
+        );
+    }
+
+    #[test]
+    fn test_ignore_rule_matches() {
+        assert!(IgnoreRule::Url.matches("file:///tmp/x"));
+        assert!(IgnoreRule::Email.matches("a.b@c.io"));
+        assert!(!IgnoreRule::Email.matches("@handle"));
+        assert!(IgnoreRule::Hex.matches("DEADBEEF01"));
+        assert!(!IgnoreRule::Hex.matches("facade"));
+        assert!(IgnoreRule::Acronym.matches("APIs"));
+        assert!(!IgnoreRule::Acronym.matches("I"));
+        assert!(!IgnoreRule::Acronym.matches("Hello"));
+    }
+}
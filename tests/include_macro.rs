@@ -43,3 +43,161 @@ fn test_include_macro_all_and_ordering() {
     // Expect that "test" is among returned results for this query.
     assert!(all.iter().any(|s| s.term == "test"));
 }
+
+#[test]
+fn test_include_macro_word_lengths() {
+    let sym = include_dictionary!("tests/data/words.txt", max_distance = 2, lowercase = true);
+
+    // Shortest words are "be"/"to", the longest is "symspell".
+    assert_eq!(sym.word_lengths(), Some((2, 8)));
+    assert!(sym.lookup("symspellings", 2, Verbosity::All).is_empty());
+    assert_eq!(sym.lookup("symspel", 2, Verbosity::Top)[0].term, "symspell");
+}
+
+/// Levenshtein distance after lower-casing, which the deletion index (built from
+/// lower-case words) cannot find candidates for.
+struct CaseInsensitive;
+
+impl symspellrs::DistanceMetric for CaseInsensitive {
+    fn distance(&self, a: &str, b: &str) -> u8 {
+        symspellrs::Levenshtein.distance(&a.to_lowercase(), &b.to_lowercase())
+    }
+}
+
+#[test]
+fn test_include_macro_fallback() {
+    use symspellrs::{Fallback, Origin};
+
+    let sym = include_dictionary!("tests/data/words.txt", max_distance = 2, lowercase = true)
+        .with_metric(CaseInsensitive);
+
+    // The deletion index finds nothing for upper-case input; the default full scan does.
+    let top = sym.lookup("HELLOO", 2, Verbosity::Top);
+    assert_eq!(top[0].term, "hello");
+    assert_eq!(top[0].distance, 1);
+    assert_eq!(top[0].origin, Origin::FallbackScan);
+
+    let bucketed = sym.with_fallback(Fallback::LengthBucketed);
+    let closest = bucketed.lookup("APPL", 2, Verbosity::Closest);
+    let terms: Vec<&str> = closest.iter().map(|s| s.term.as_str()).collect();
+    assert_eq!(terms, ["apple", "apply"]);
+    assert!(closest.iter().all(|s| s.origin == Origin::FallbackScan));
+
+    let bounded = bucketed.with_fallback(Fallback::Bounded {
+        max_words: 0,
+        max_time: None,
+    });
+    assert!(bounded.lookup("HELO", 2, Verbosity::Top).is_empty());
+
+    let disabled = bounded.with_fallback(Fallback::Disabled);
+    assert!(disabled.lookup("HELO", 2, Verbosity::Top).is_empty());
+    // Index matches are unaffected by the fallback setting.
+    assert_eq!(
+        disabled.lookup("helo", 2, Verbosity::Top)[0].origin,
+        Origin::EditDistance
+    );
+}
+
+#[test]
+fn test_include_macro_user_layer() {
+    use symspellrs::{LayerPriority, LayeredDictionary};
+
+    let sym = include_dictionary!("tests/data/words.txt", max_distance = 2, lowercase = true);
+    let mut dict = LayeredDictionary::new(sym).with_priority(LayerPriority::User);
+    assert!(dict.lookup("symspellrs", 2, Verbosity::Top)[0].distance > 0);
+
+    dict.add_word("symspellrs");
+    let top = dict.lookup("symspellrs", 2, Verbosity::Top);
+    assert_eq!((top[0].term.as_str(), top[0].distance), ("symspellrs", 0));
+    // Base words are still found, and the embedded dictionary is untouched.
+    assert_eq!(dict.lookup("wrld", 2, Verbosity::Top)[0].term, "world");
+    assert!(!dict.base().contains("symspellrs"));
+}
+
+#[test]
+fn test_include_macro_exclude() {
+    use symspellrs::Lookup;
+
+    // Excluded words are dropped from the dictionary...
+    let sym = include_dictionary!(
+        "tests/data/words.txt",
+        max_distance = 2,
+        lowercase = true,
+        exclude = "tests/data/blocked.txt"
+    );
+    assert!(!sym.contains("hell") && !sym.contains("tost"));
+    assert_eq!(sym.lookup("hel", 2, Verbosity::Top)[0].term, "help");
+    assert_eq!(sym.lookup("tosst", 2, Verbosity::Top)[0].term, "test");
+
+    // ...or kept as valid words that are never suggested.
+    let sym = include_dictionary!(
+        "tests/data/words.txt",
+        max_distance = 2,
+        lowercase = true,
+        exclude = "tests/data/blocked.txt",
+        keep_excluded = true
+    );
+    assert!(sym.contains("hell"));
+    assert_eq!(sym.blocklist().map(|b| b.len()), Some(2));
+    let terms: Vec<String> = sym
+        .lookup("hell", 2, Verbosity::All)
+        .into_iter()
+        .map(|s| s.term)
+        .collect();
+    assert!(!terms.is_empty() && !terms.iter().any(|t| t == "hell"));
+
+    let runtime = include_dictionary!(
+        "tests/data/words.txt",
+        max_distance = 2,
+        lowercase = true,
+        precompute = false,
+        exclude = "tests/data/blocked.txt",
+        keep_excluded = true
+    );
+    assert!(Lookup::contains(&runtime, "tost"));
+    assert_eq!(runtime.lookup("tost", 2, Verbosity::Top)[0].term, "test");
+}
+
+#[test]
+fn test_include_macro_corrections() {
+    use symspellrs::Origin;
+
+    let sym = include_dictionary!(
+        "tests/data/words.txt",
+        max_distance = 2,
+        lowercase = true,
+        corrections = "tests/data/corrections.txt"
+    );
+    assert_eq!(sym.corrections().map(|c| c.len()), Some(3));
+    // "help" is one edit from "hellp" too; the table decides.
+    let top = sym.lookup("hellp", 2, Verbosity::Top);
+    assert_eq!(top[0].term, "hello");
+    assert_eq!(top[0].origin, Origin::CorrectionTable);
+    assert_eq!(sym.lookup("teh", 2, Verbosity::Closest)[0].term, "the");
+    assert_eq!(sym.find_top("alot").unwrap().term, "a lot");
+
+    let runtime = include_dictionary!(
+        "tests/data/words.txt",
+        max_distance = 2,
+        lowercase = true,
+        precompute = false,
+        corrections = "tests/data/corrections.txt"
+    );
+    assert_eq!(runtime.lookup("hellp", 2, Verbosity::Top)[0].term, "hello");
+}
+
+#[test]
+fn test_include_bigram_dictionary() {
+    use symspellrs::{include_bigram_dictionary, ContextualLookup};
+
+    let sym = include_dictionary!("tests/data/words.txt", max_distance = 2, lowercase = true);
+    let bigrams = include_bigram_dictionary!("tests/data/bigrams.txt", lowercase = true);
+    assert_eq!(bigrams.frequency("the", "test"), Some(150));
+    let dict = ContextualLookup::new(sym, bigrams);
+
+    // "test" and "tost" are equally frequent and one edit from "tast"; the
+    // preceding "the" favours the more common pair.
+    let after_the = dict.lookup_context("tast", Some("the"), None, 2, Verbosity::Closest);
+    let terms: Vec<&str> = after_the.iter().map(|s| s.term.as_str()).collect();
+    assert_eq!(terms, ["test", "tost"]);
+}